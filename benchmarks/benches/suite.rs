@@ -8,7 +8,7 @@ use criterion::{
     black_box, criterion_group, criterion_main, BatchSize, Bencher, BenchmarkId, Criterion,
 };
 use fnv::FnvBuildHasher;
-use kempt::Map;
+use kempt::{FrozenMap, Map};
 use rand::distributions::Standard;
 use rand::prelude::Distribution;
 use rand::rngs::StdRng;
@@ -63,6 +63,22 @@ where
     });
 }
 
+fn frozen_lookup<Key>(bench: &mut Bencher, keys: &[Key])
+where
+    Key: Clone + Ord,
+{
+    let set = keys
+        .iter()
+        .map(|key| (key.clone(), ()))
+        .collect::<FrozenMap<Key, ()>>();
+    let mut keys = keys.iter().cycle();
+
+    bench.iter(|| {
+        let key = black_box(keys.next().expect("cycled"));
+        assert!(set.get(key).is_some());
+    });
+}
+
 fn lookup<Key>(c: &mut Criterion, keys: &[Key], sizes: &[usize])
 where
     Key: Eq + Hash + Clone + Ord + Default + From<u8> + TryFrom<usize> + AddAssign,
@@ -86,6 +102,28 @@ where
     }
 }
 
+fn frozen<Key>(c: &mut Criterion, keys: &[Key], sizes: &[usize])
+where
+    Key: Clone + Ord + TryFrom<usize>,
+{
+    let mut group = c.benchmark_group(format!("frozen {}", type_name::<Key>()));
+    for limit in sizes.iter().copied() {
+        if Key::try_from(limit).is_err() || limit > keys.len() {
+            break;
+        }
+        group.bench_with_input(
+            BenchmarkId::new("object", limit),
+            &keys[..limit],
+            object_lookup,
+        );
+        group.bench_with_input(
+            BenchmarkId::new("frozen", limit),
+            &keys[..limit],
+            frozen_lookup,
+        );
+    }
+}
+
 fn btree_fill<Key>(bench: &mut Bencher, (keys, starting_size): &(&[Key], usize))
 where
     Key: Clone + Ord,
@@ -264,6 +302,7 @@ where
     let keys = generate_keys::<Key>(max, true, random_seed);
     fill::<Key>(c, &keys, sizes, "fill-rdm");
     lookup::<Key>(c, &keys, sizes);
+    frozen::<Key>(c, &keys, &[100, 1_000, 10_000]);
     remove::<Key>(c, &keys, sizes);
     let keys = generate_keys::<Key>(max, false, random_seed);
     fill::<Key>(c, &keys, sizes, "fill-seq");
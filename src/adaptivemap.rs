@@ -0,0 +1,834 @@
+use alloc::collections::{btree_map, BTreeMap};
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+use core::iter::FusedIterator;
+use core::mem;
+use core::ops::{Deref, DerefMut, RangeBounds};
+
+use crate::map::{self, Field};
+use crate::{Map, Sort};
+
+/// An ordered map that stores its fields in a [`Map`] while it is small, and in
+/// a [`BTreeMap`] once it grows beyond a threshold.
+///
+/// [`Map`] outperforms [`BTreeMap`] for small collections, but inserting into
+/// and searching a [`Map`] become slower than a [`BTreeMap`] as the collection
+/// grows. This type switches to a [`BTreeMap`] once its length exceeds its
+/// threshold, and switches back to a [`Map`] once its length drops to half of
+/// its threshold or below. The gap between the two lengths prevents a collection
+/// whose length hovers around the threshold from switching repeatedly.
+///
+/// Because its fields may be stored in a [`BTreeMap`], this type returns and
+/// iterates over keys and values as tuples rather than as [`Field`]s.
+///
+/// The default threshold is [`AdaptiveMap::DEFAULT_THRESHOLD`]. Collections
+/// with insert-heavy workloads may benefit from a lower threshold, which can be
+/// provided using [`with_threshold()`](Self::with_threshold).
+///
+/// ```rust
+/// use kempt::AdaptiveMap;
+///
+/// let mut map = AdaptiveMap::with_threshold(4);
+/// map.extend((0..4).map(|n| (n, n)));
+/// assert!(!map.is_tree());
+/// map.insert(4, 4);
+/// assert!(map.is_tree());
+/// assert_eq!(map.get(&2), Some(&2));
+///
+/// for n in 0..4 {
+///     map.remove(&n);
+/// }
+/// assert!(!map.is_tree());
+/// ```
+#[derive(Clone)]
+pub struct AdaptiveMap<K, V>
+where
+    K: Ord,
+{
+    storage: Storage<K, V>,
+    threshold: usize,
+}
+
+#[derive(Clone)]
+enum Storage<K, V>
+where
+    K: Ord,
+{
+    Map(Map<K, V>),
+    Tree(BTreeMap<K, V>),
+}
+
+impl<K, V> Default for AdaptiveMap<K, V>
+where
+    K: Ord,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> AdaptiveMap<K, V>
+where
+    K: Ord,
+{
+    /// The threshold used by [`AdaptiveMap::new`].
+    pub const DEFAULT_THRESHOLD: usize = 1_024;
+
+    /// Returns an empty map that uses [`Self::DEFAULT_THRESHOLD`].
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self::with_threshold(Self::DEFAULT_THRESHOLD)
+    }
+
+    /// Returns an empty map that switches to a [`BTreeMap`] once it contains
+    /// more than `threshold` fields, and back to a [`Map`] once it contains
+    /// `threshold / 2` fields or fewer.
+    ///
+    /// A threshold of 0 or 1 stores every map containing more than one field
+    /// in a [`BTreeMap`], and only switches back to a [`Map`] once the map is
+    /// empty.
+    #[must_use]
+    #[inline]
+    pub const fn with_threshold(threshold: usize) -> Self {
+        Self {
+            storage: Storage::Map(Map::new()),
+            threshold,
+        }
+    }
+
+    /// Returns the number of fields this map can contain before switching to a
+    /// [`BTreeMap`].
+    #[must_use]
+    #[inline]
+    pub const fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Returns true if this map is currently stored in a [`BTreeMap`].
+    #[must_use]
+    #[inline]
+    pub const fn is_tree(&self) -> bool {
+        matches!(self.storage, Storage::Tree(_))
+    }
+
+    /// Inserts `key` and `value`. If an entry already existed for `key`, the
+    /// key and value being overwritten are returned.
+    #[inline]
+    pub fn insert(&mut self, key: K, value: V) -> Option<(K, V)> {
+        let replaced = match &mut self.storage {
+            Storage::Map(map) => map.insert(key, value).map(Field::into_parts),
+            Storage::Tree(tree) => {
+                let replaced = tree.remove_entry(&key);
+                tree.insert(key, value);
+                replaced
+            }
+        };
+        self.adapt();
+        replaced
+    }
+
+    /// Returns an [`Entry`] for `key`.
+    ///
+    /// ```rust
+    /// use kempt::AdaptiveMap;
+    ///
+    /// let mut counts = AdaptiveMap::with_threshold(1);
+    /// for word in ["a", "b", "a"] {
+    ///     *counts.entry(word).or_default() += 1;
+    /// }
+    /// assert!(counts.is_tree());
+    /// assert_eq!(counts.get(&"a"), Some(&2));
+    /// ```
+    #[inline]
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        // Entries can remove fields without access to this map, so the storage
+        // is checked before creating each entry.
+        self.adapt();
+        let index = match &self.storage {
+            Storage::Map(fields) => match fields.find_key_index(&key) {
+                Ok(index) => index,
+                Err(_) => return Entry::Vacant(VacantEntry(VacantStorage::Map { map: self, key })),
+            },
+            // Trees look up the key using their own entry API.
+            Storage::Tree(_) => 0,
+        };
+        match &mut self.storage {
+            Storage::Map(fields) => Entry::Occupied(OccupiedEntry(OccupiedStorage::Map(
+                map::OccupiedEntry::new(fields, index),
+            ))),
+            Storage::Tree(tree) => match tree.entry(key) {
+                btree_map::Entry::Occupied(entry) => {
+                    Entry::Occupied(OccupiedEntry(OccupiedStorage::Tree(entry)))
+                }
+                btree_map::Entry::Vacant(entry) => {
+                    Entry::Vacant(VacantEntry(VacantStorage::Tree(entry)))
+                }
+            },
+        }
+    }
+
+    /// Returns true if this map contains `key`.
+    #[inline]
+    pub fn contains<SearchFor>(&self, key: &SearchFor) -> bool
+    where
+        K: Borrow<SearchFor> + Sort<SearchFor>,
+        SearchFor: Ord + ?Sized,
+    {
+        match &self.storage {
+            Storage::Map(map) => map.contains(key),
+            Storage::Tree(tree) => tree.contains_key(key),
+        }
+    }
+
+    /// Returns the value associated with `key`, if found.
+    #[inline]
+    pub fn get<SearchFor>(&self, key: &SearchFor) -> Option<&V>
+    where
+        K: Borrow<SearchFor> + Sort<SearchFor>,
+        SearchFor: Ord + ?Sized,
+    {
+        match &self.storage {
+            Storage::Map(map) => map.get(key),
+            Storage::Tree(tree) => tree.get(key),
+        }
+    }
+
+    /// Returns a mutable reference to the value associated with `key`, if
+    /// found.
+    #[inline]
+    pub fn get_mut<SearchFor>(&mut self, key: &SearchFor) -> Option<&mut V>
+    where
+        K: Borrow<SearchFor> + Sort<SearchFor>,
+        SearchFor: Ord + ?Sized,
+    {
+        match &mut self.storage {
+            Storage::Map(map) => map.get_mut(key),
+            Storage::Tree(tree) => tree.get_mut(key),
+        }
+    }
+
+    /// Returns the key and value associated with `key`, if found.
+    #[inline]
+    pub fn get_key_value<SearchFor>(&self, key: &SearchFor) -> Option<(&K, &V)>
+    where
+        K: Borrow<SearchFor> + Sort<SearchFor>,
+        SearchFor: Ord + ?Sized,
+    {
+        match &self.storage {
+            Storage::Map(map) => map.get_field(key).map(|field| (field.key(), &field.value)),
+            Storage::Tree(tree) => tree.get_key_value(key),
+        }
+    }
+
+    /// Removes the key and value associated with `key`, if found.
+    #[inline]
+    pub fn remove<SearchFor>(&mut self, key: &SearchFor) -> Option<(K, V)>
+    where
+        K: Borrow<SearchFor> + Sort<SearchFor>,
+        SearchFor: Ord + ?Sized,
+    {
+        let removed = match &mut self.storage {
+            Storage::Map(map) => map.remove(key).map(Field::into_parts),
+            Storage::Tree(tree) => tree.remove_entry(key),
+        };
+        self.adapt();
+        removed
+    }
+
+    /// Returns the number of fields in this map.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        match &self.storage {
+            Storage::Map(map) => map.len(),
+            Storage::Tree(tree) => tree.len(),
+        }
+    }
+
+    /// Returns true if this map contains no fields.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the keys and values in this map.
+    #[must_use]
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.into_iter()
+    }
+
+    /// Returns an iterator over the keys and mutable values in this map.
+    #[must_use]
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.into_iter()
+    }
+
+    /// Returns an iterator over the keys in this map.
+    #[must_use]
+    #[inline]
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> + ExactSizeIterator + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Returns an iterator over the values in this map.
+    #[must_use]
+    #[inline]
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> + ExactSizeIterator + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /// Returns an iterator over the keys and values whose keys are contained in
+    /// `range`.
+    #[inline]
+    pub fn range<SearchFor, R>(&self, range: R) -> Range<'_, K, V>
+    where
+        K: Borrow<SearchFor> + Sort<SearchFor>,
+        R: RangeBounds<SearchFor>,
+        SearchFor: Ord + ?Sized,
+    {
+        Range(match &self.storage {
+            Storage::Map(map) => IterStorage::Map(map.range(range)),
+            Storage::Tree(tree) => IterStorage::Tree(tree.range(range)),
+        })
+    }
+
+    /// Removes all fields from this map.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.storage = Storage::Map(Map::new());
+    }
+
+    /// Switches the storage of this map if its length is outside of the range
+    /// allowed by its current storage.
+    fn adapt(&mut self) {
+        let len = self.len();
+        let switch = match &self.storage {
+            Storage::Map(_) => len > self.threshold,
+            Storage::Tree(_) => len <= self.threshold / 2,
+        };
+        if switch {
+            self.switch_storage();
+        }
+    }
+
+    /// Moves the fields of this map into the other kind of storage.
+    fn switch_storage(&mut self) {
+        self.storage = match mem::replace(&mut self.storage, Storage::Map(Map::new())) {
+            Storage::Map(map) => Storage::Tree(BTreeMap::from(map)),
+            Storage::Tree(tree) => Storage::Map(Map::from(tree)),
+        };
+    }
+}
+
+impl<K, V> PartialEq for AdaptiveMap<K, V>
+where
+    K: Ord,
+    V: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<K, V> Eq for AdaptiveMap<K, V>
+where
+    K: Ord,
+    V: Eq,
+{
+}
+
+impl<K, V> Debug for AdaptiveMap<K, V>
+where
+    K: Ord + Debug,
+    V: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V> From<Map<K, V>> for AdaptiveMap<K, V>
+where
+    K: Ord,
+{
+    #[inline]
+    fn from(map: Map<K, V>) -> Self {
+        let mut this = Self {
+            storage: Storage::Map(map),
+            threshold: Self::DEFAULT_THRESHOLD,
+        };
+        this.adapt();
+        this
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for AdaptiveMap<K, V>
+where
+    K: Ord,
+{
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::from(Map::from_iter(iter))
+    }
+}
+
+impl<K, V> Extend<(K, V)> for AdaptiveMap<K, V>
+where
+    K: Ord,
+{
+    #[inline]
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a AdaptiveMap<K, V>
+where
+    K: Ord,
+{
+    type IntoIter = Iter<'a, K, V>;
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        Iter(match &self.storage {
+            Storage::Map(map) => IterStorage::Map(map.iter()),
+            Storage::Tree(tree) => IterStorage::Tree(tree.iter()),
+        })
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a mut AdaptiveMap<K, V>
+where
+    K: Ord,
+{
+    type IntoIter = IterMut<'a, K, V>;
+    type Item = (&'a K, &'a mut V);
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IterMut(match &mut self.storage {
+            Storage::Map(map) => IterStorage::Map(map.iter_mut()),
+            Storage::Tree(tree) => IterStorage::Tree(tree.iter_mut()),
+        })
+    }
+}
+
+impl<K, V> IntoIterator for AdaptiveMap<K, V>
+where
+    K: Ord,
+{
+    type IntoIter = IntoIter<K, V>;
+    type Item = (K, V);
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(match self.storage {
+            Storage::Map(map) => IterStorage::Map(map.into_iter()),
+            Storage::Tree(tree) => IterStorage::Tree(tree.into_iter()),
+        })
+    }
+}
+
+/// An entry in an [`AdaptiveMap`].
+pub enum Entry<'a, K, V>
+where
+    K: Ord,
+{
+    /// An entry whose key is contained in the map.
+    Occupied(OccupiedEntry<'a, K, V>),
+    /// An entry whose key is not contained in the map.
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+impl<'a, K, V> Entry<'a, K, V>
+where
+    K: Ord,
+{
+    /// Calls `update` with the value of this entry if it is occupied.
+    #[must_use]
+    #[inline]
+    pub fn and_modify(self, update: impl FnOnce(&mut V)) -> Self {
+        match self {
+            Entry::Occupied(mut entry) => {
+                update(&mut entry);
+                Entry::Occupied(entry)
+            }
+            vacant @ Entry::Vacant(_) => vacant,
+        }
+    }
+
+    /// Returns a mutable reference to the value of this entry, inserting the
+    /// result of `contents()` if this entry is vacant.
+    #[inline]
+    pub fn or_insert_with(self, contents: impl FnOnce() -> V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(contents()),
+        }
+    }
+
+    /// Returns a mutable reference to the value of this entry, inserting
+    /// `value` if this entry is vacant.
+    #[inline]
+    pub fn or_insert(self, value: V) -> &'a mut V {
+        self.or_insert_with(|| value)
+    }
+
+    /// Returns a mutable reference to the value of this entry, inserting
+    /// `V::default()` if this entry is vacant.
+    #[inline]
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+}
+
+/// An entry in an [`AdaptiveMap`] whose key is contained in the map.
+pub struct OccupiedEntry<'a, K, V>(OccupiedStorage<'a, K, V>)
+where
+    K: Ord;
+
+enum OccupiedStorage<'a, K, V>
+where
+    K: Ord,
+{
+    Map(map::OccupiedEntry<'a, K, V>),
+    Tree(btree_map::OccupiedEntry<'a, K, V>),
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V>
+where
+    K: Ord,
+{
+    /// Converts this entry into a mutable reference to the value.
+    #[must_use]
+    #[inline]
+    pub fn into_mut(self) -> &'a mut V {
+        match self.0 {
+            OccupiedStorage::Map(entry) => entry.into_mut(),
+            OccupiedStorage::Tree(entry) => entry.into_mut(),
+        }
+    }
+
+    /// Returns the key of this entry.
+    #[must_use]
+    #[inline]
+    pub fn key(&self) -> &K {
+        match &self.0 {
+            OccupiedStorage::Map(entry) => entry.key(),
+            OccupiedStorage::Tree(entry) => entry.key(),
+        }
+    }
+
+    /// Replaces the value of this entry with `value`, and returns the existing
+    /// value.
+    #[inline]
+    pub fn replace(self, value: V) -> V {
+        mem::replace(self.into_mut(), value)
+    }
+
+    /// Removes this entry from the map, and returns its key and value.
+    ///
+    /// The map does not switch its storage until it is next modified.
+    #[must_use]
+    #[inline]
+    pub fn remove(self) -> (K, V) {
+        match self.0 {
+            OccupiedStorage::Map(entry) => entry.remove().into_parts(),
+            OccupiedStorage::Tree(entry) => entry.remove_entry(),
+        }
+    }
+}
+
+impl<K, V> Deref for OccupiedEntry<'_, K, V>
+where
+    K: Ord,
+{
+    type Target = V;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        match &self.0 {
+            OccupiedStorage::Map(entry) => entry,
+            OccupiedStorage::Tree(entry) => entry.get(),
+        }
+    }
+}
+
+impl<K, V> DerefMut for OccupiedEntry<'_, K, V>
+where
+    K: Ord,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match &mut self.0 {
+            OccupiedStorage::Map(entry) => entry,
+            OccupiedStorage::Tree(entry) => entry.get_mut(),
+        }
+    }
+}
+
+/// An entry in an [`AdaptiveMap`] whose key is not contained in the map.
+pub struct VacantEntry<'a, K, V>(VacantStorage<'a, K, V>)
+where
+    K: Ord;
+
+enum VacantStorage<'a, K, V>
+where
+    K: Ord,
+{
+    Map {
+        map: &'a mut AdaptiveMap<K, V>,
+        key: K,
+    },
+    Tree(btree_map::VacantEntry<'a, K, V>),
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V>
+where
+    K: Ord,
+{
+    /// Returns the key of this entry.
+    #[must_use]
+    #[inline]
+    pub fn key(&self) -> &K {
+        match &self.0 {
+            VacantStorage::Map { key, .. } => key,
+            VacantStorage::Tree(entry) => entry.key(),
+        }
+    }
+
+    /// Inserts `value` into the map, returning a mutable reference to the
+    /// inserted value.
+    ///
+    /// If inserting `value` causes the map to exceed its threshold, the map
+    /// switches to a [`BTreeMap`] before `value` is inserted.
+    #[inline]
+    pub fn insert(self, value: V) -> &'a mut V {
+        match self.0 {
+            VacantStorage::Map { map, key } => {
+                if map.len() >= map.threshold {
+                    map.switch_storage();
+                }
+                match &mut map.storage {
+                    Storage::Map(fields) => fields.get_or_insert_with(key, || value),
+                    Storage::Tree(tree) => tree.entry(key).or_insert(value),
+                }
+            }
+            VacantStorage::Tree(entry) => entry.insert(value),
+        }
+    }
+}
+
+enum IterStorage<M, T> {
+    Map(M),
+    Tree(T),
+}
+
+macro_rules! impl_iterator {
+    ($name:ident, $item:ty, $map_item:expr, $($lifetime:lifetime)?) => {
+        impl<$($lifetime,)? K, V> Iterator for $name<$($lifetime,)? K, V> {
+            type Item = $item;
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                match &mut self.0 {
+                    IterStorage::Map(iter) => iter.next().map($map_item),
+                    IterStorage::Tree(iter) => iter.next(),
+                }
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                match &self.0 {
+                    IterStorage::Map(iter) => iter.size_hint(),
+                    IterStorage::Tree(iter) => iter.size_hint(),
+                }
+            }
+        }
+
+        impl<$($lifetime,)? K, V> DoubleEndedIterator for $name<$($lifetime,)? K, V> {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                match &mut self.0 {
+                    IterStorage::Map(iter) => iter.next_back().map($map_item),
+                    IterStorage::Tree(iter) => iter.next_back(),
+                }
+            }
+        }
+
+        impl<$($lifetime,)? K, V> FusedIterator for $name<$($lifetime,)? K, V> {}
+    };
+}
+
+/// An iterator over the keys and values in an [`AdaptiveMap`].
+///
+/// This iterator is guaranteed to return results in the sort order of the `K`
+/// type.
+pub struct Iter<'a, K, V>(IterStorage<map::Iter<'a, K, V>, btree_map::Iter<'a, K, V>>);
+
+impl_iterator!(
+    Iter,
+    (&'a K, &'a V),
+    |field: &'a Field<K, V>| (field.key(), &field.value),
+    'a
+);
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+/// An iterator over the keys and mutable values in an [`AdaptiveMap`].
+///
+/// This iterator is guaranteed to return results in the sort order of the `K`
+/// type.
+pub struct IterMut<'a, K, V>(IterStorage<map::IterMut<'a, K, V>, btree_map::IterMut<'a, K, V>>);
+
+impl_iterator!(
+    IterMut,
+    (&'a K, &'a mut V),
+    |item| item,
+    'a
+);
+
+impl<K, V> ExactSizeIterator for IterMut<'_, K, V> {}
+
+/// An iterator that converts an [`AdaptiveMap`] into its keys and values.
+///
+/// This iterator is guaranteed to return results in the sort order of the `K`
+/// type.
+pub struct IntoIter<K, V>(IterStorage<map::IntoIter<K, V>, btree_map::IntoIter<K, V>>);
+
+impl_iterator!(IntoIter, (K, V), Field::into_parts,);
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+/// An iterator over the keys and values in a range of an [`AdaptiveMap`].
+///
+/// This iterator is guaranteed to return results in the sort order of the `K`
+/// type.
+pub struct Range<'a, K, V>(IterStorage<map::Iter<'a, K, V>, btree_map::Range<'a, K, V>>);
+
+impl_iterator!(
+    Range,
+    (&'a K, &'a V),
+    |field: &'a Field<K, V>| (field.key(), &field.value),
+    'a
+);
+
+#[test]
+fn switching() {
+    let mut map = AdaptiveMap::with_threshold(4);
+    assert_eq!(map.threshold(), 4);
+    for key in 0..4 {
+        assert_eq!(map.insert(key, key), None);
+    }
+    assert!(!map.is_tree());
+    assert_eq!(map.insert(4, 4), None);
+    assert!(map.is_tree());
+    assert_eq!(map.insert(4, 40), Some((4, 4)));
+    assert_eq!(map.get_key_value(&4), Some((&4, &40)));
+
+    // The map stays a tree until it shrinks to half of the threshold.
+    assert_eq!(map.remove(&0), Some((0, 0)));
+    assert_eq!(map.remove(&1), Some((1, 1)));
+    assert!(map.is_tree());
+    assert_eq!(map.remove(&2), Some((2, 2)));
+    assert!(!map.is_tree());
+    assert!(map.iter().eq([(&3, &3), (&4, &40)]));
+
+    map.extend((10..20).map(|key| (key, key)));
+    assert!(map.is_tree());
+    map.clear();
+    assert!(!map.is_tree());
+    assert!(map.is_empty());
+
+    // Small thresholds switch back once the map is empty.
+    for threshold in 0..=1 {
+        let mut map = AdaptiveMap::with_threshold(threshold);
+        map.extend([(1, 1), (2, 2)]);
+        assert!(map.is_tree());
+        map.remove(&1);
+        assert!(map.is_tree());
+        map.remove(&2);
+        assert!(!map.is_tree());
+    }
+}
+
+#[test]
+fn entries() {
+    let mut map = AdaptiveMap::with_threshold(4);
+    for key in [1, 2, 1, 3, 3, 3, 4, 5] {
+        map.entry(key).and_modify(|count| *count += 1).or_insert(1);
+    }
+    assert!(map.is_tree());
+    assert!(map
+        .iter()
+        .eq([(&1, &2), (&2, &1), (&3, &3), (&4, &1), (&5, &1)]));
+
+    let Entry::Occupied(entry) = map.entry(1) else {
+        unreachable!("1 was inserted")
+    };
+    assert_eq!(entry.key(), &1);
+    assert_eq!(entry.replace(5), 2);
+    for (key, value) in [(1, 5), (2, 1), (4, 1)] {
+        let Entry::Occupied(entry) = map.entry(key) else {
+            unreachable!("{key} was inserted")
+        };
+        assert_eq!(entry.remove(), (key, value));
+    }
+    // Removing through an entry switches storage on the next modification.
+    assert!(map.is_tree());
+    let Entry::Vacant(entry) = map.entry(6) else {
+        unreachable!("6 was not inserted")
+    };
+    assert_eq!(entry.key(), &6);
+    *entry.insert(6) += 1;
+    assert!(!map.is_tree());
+    assert!(map.iter().eq([(&3, &3), (&5, &1), (&6, &7)]));
+
+    // Inserting into a vacant entry of a full map switches to a tree.
+    map.insert(7, 7);
+    let Entry::Vacant(entry) = map.entry(9) else {
+        unreachable!("9 was not inserted")
+    };
+    assert_eq!(*entry.insert(9), 9);
+    assert!(map.is_tree());
+    assert_eq!(map.len(), 5);
+}
+
+#[test]
+fn iteration() {
+    for threshold in [0, 100] {
+        let mut map = AdaptiveMap::with_threshold(threshold);
+        map.extend((0..10).map(|key| (key, key * 10)));
+        assert_eq!(map.is_tree(), threshold == 0);
+        for (_, value) in &mut map {
+            *value += 1;
+        }
+        assert_eq!(map.iter().len(), 10);
+        assert!(map.keys().rev().copied().eq((0..10).rev()));
+        assert!(map.values().copied().eq((0..10).map(|key| key * 10 + 1)));
+        assert!(map.range(3..5).eq([(&3, &31), (&4, &41)]));
+        assert!(map.range(..=1).rev().eq([(&1, &11), (&0, &1)]));
+        assert!(map.contains(&9));
+        *map.get_mut(&9).unwrap() = 0;
+        assert_eq!(map.get(&9), Some(&0));
+        assert_eq!(map.clone(), map);
+        assert!(map.into_iter().map(|(key, _)| key).eq(0..10));
+    }
+    let map = (0..2000)
+        .map(|key| (key, ()))
+        .collect::<AdaptiveMap<_, _>>();
+    assert!(map.is_tree());
+}
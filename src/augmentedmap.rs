@@ -0,0 +1,1169 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Debug};
+use core::marker::PhantomData;
+use core::mem;
+use core::ops::{Add, Deref, DerefMut, Range, RangeBounds};
+
+use crate::map::{self, Field};
+use crate::{Map, Sort};
+
+/// The target number of consecutive fields summarized by each leaf of the
+/// summary tree. Blocks are split when they grow past twice this length and
+/// merged with a neighbor when they shrink below half of it.
+const BLOCK_LEN: usize = 16;
+
+/// An associative operation with an identity, used by [`AugmentedMap`] to
+/// summarize its fields.
+///
+/// Implementors must guarantee that `combine` is associative and that
+/// combining any summary with `identity()` produces an equivalent summary.
+/// `combine` does not need to be commutative, as summaries are always combined
+/// in the sort order of their keys.
+///
+/// ```rust
+/// use kempt::augmentedmap::Monoid;
+/// use kempt::AugmentedMap;
+///
+/// /// Tracks the longest value.
+/// struct Longest;
+///
+/// impl<K> Monoid<K, &'static str> for Longest {
+///     type Summary = usize;
+///
+///     fn identity() -> usize {
+///         0
+///     }
+///
+///     fn summarize(_key: &K, value: &&'static str) -> usize {
+///         value.len()
+///     }
+///
+///     fn combine(left: &usize, right: &usize) -> usize {
+///         *left.max(right)
+///     }
+/// }
+///
+/// let names = AugmentedMap::<u32, &'static str, Longest>::from_iter([
+///     (1, "a"),
+///     (2, "abc"),
+///     (3, "ab"),
+/// ]);
+/// assert_eq!(names.aggregate(1..=2), 3);
+/// assert_eq!(names.aggregate(3..), 2);
+/// ```
+pub trait Monoid<K, V> {
+    /// The summary of zero or more fields.
+    type Summary: Clone;
+
+    /// Returns the summary of zero fields.
+    fn identity() -> Self::Summary;
+
+    /// Returns the summary of a single field.
+    fn summarize(key: &K, value: &V) -> Self::Summary;
+
+    /// Returns the summary of the fields summarized by `left` followed by the
+    /// fields summarized by `right`.
+    fn combine(left: &Self::Summary, right: &Self::Summary) -> Self::Summary;
+}
+
+/// A [`Monoid`] that sums the values of an [`AugmentedMap`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub struct Sum;
+
+impl<K, V> Monoid<K, V> for Sum
+where
+    V: Clone + Default + Add<Output = V>,
+{
+    type Summary = V;
+
+    #[inline]
+    fn identity() -> V {
+        V::default()
+    }
+
+    #[inline]
+    fn summarize(_key: &K, value: &V) -> V {
+        value.clone()
+    }
+
+    #[inline]
+    fn combine(left: &V, right: &V) -> V {
+        left.clone() + right.clone()
+    }
+}
+
+/// A [`Monoid`] that counts the fields of an [`AugmentedMap`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub struct Count;
+
+impl<K, V> Monoid<K, V> for Count {
+    type Summary = usize;
+
+    #[inline]
+    fn identity() -> usize {
+        0
+    }
+
+    #[inline]
+    fn summarize(_key: &K, _value: &V) -> usize {
+        1
+    }
+
+    #[inline]
+    fn combine(left: &usize, right: &usize) -> usize {
+        left + right
+    }
+}
+
+/// A [`Monoid`] that finds the smallest value of an [`AugmentedMap`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub struct Min;
+
+impl<K, V> Monoid<K, V> for Min
+where
+    V: Ord + Clone,
+{
+    type Summary = Option<V>;
+
+    #[inline]
+    fn identity() -> Option<V> {
+        None
+    }
+
+    #[inline]
+    fn summarize(_key: &K, value: &V) -> Option<V> {
+        Some(value.clone())
+    }
+
+    #[inline]
+    fn combine(left: &Option<V>, right: &Option<V>) -> Option<V> {
+        match (left, right) {
+            (Some(left), Some(right)) => Some(left.min(right).clone()),
+            (Some(value), None) | (None, Some(value)) => Some(value.clone()),
+            (None, None) => None,
+        }
+    }
+}
+
+/// A [`Monoid`] that finds the largest value of an [`AugmentedMap`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Default)]
+pub struct Max;
+
+impl<K, V> Monoid<K, V> for Max
+where
+    V: Ord + Clone,
+{
+    type Summary = Option<V>;
+
+    #[inline]
+    fn identity() -> Option<V> {
+        None
+    }
+
+    #[inline]
+    fn summarize(_key: &K, value: &V) -> Option<V> {
+        Some(value.clone())
+    }
+
+    #[inline]
+    fn combine(left: &Option<V>, right: &Option<V>) -> Option<V> {
+        match (left, right) {
+            (Some(left), Some(right)) => Some(left.max(right).clone()),
+            (Some(value), None) | (None, Some(value)) => Some(value.clone()),
+            (None, None) => None,
+        }
+    }
+}
+
+/// An ordered map that maintains summaries of its fields using a [`Monoid`],
+/// allowing ranges of fields to be aggregated without visiting every field.
+///
+/// Fields are stored in a [`Map`]. The fields are split into blocks of 8 to 32
+/// fields, and the length and summary of each block is stored in a segment
+/// tree. Aggregating a range combines at most two partial blocks and
+/// `O(log n)` tree nodes. Inserting, removing, or modifying a field only
+/// resummarizes the block containing it and that block's ancestors.
+///
+/// ```rust
+/// use kempt::augmentedmap::Sum;
+/// use kempt::AugmentedMap;
+///
+/// let mut sales = AugmentedMap::<u32, u64, Sum>::new();
+/// sales.insert(1, 10);
+/// sales.insert(2, 20);
+/// sales.insert(5, 50);
+/// assert_eq!(sales.aggregate(1..5), 30);
+/// *sales.get_mut(&2).unwrap() += 5;
+/// assert_eq!(sales.aggregate(2..), 75);
+/// assert_eq!(sales.total(), 85);
+/// ```
+pub struct AugmentedMap<K, V, M>
+where
+    K: Sort<K>,
+    M: Monoid<K, V>,
+{
+    fields: Map<K, V>,
+    /// A segment tree whose root is at index 1 and whose leaves are the
+    /// blocks, starting at `tree.len() / 2`. Each node stores the number of
+    /// fields beneath it, which is used to find the block containing an index.
+    tree: Vec<Node<M::Summary>>,
+    /// The number of leaves of `tree` that are in use.
+    blocks: usize,
+    monoid: PhantomData<M>,
+}
+
+/// A node in the summary tree of an [`AugmentedMap`].
+#[derive(Clone)]
+struct Node<S> {
+    len: usize,
+    summary: S,
+}
+
+impl<K, V, M> Default for AugmentedMap<K, V, M>
+where
+    K: Sort<K>,
+    M: Monoid<K, V>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, M> AugmentedMap<K, V, M>
+where
+    K: Sort<K>,
+    M: Monoid<K, V>,
+{
+    /// Returns an empty map.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            fields: Map::new(),
+            tree: Vec::new(),
+            blocks: 0,
+            monoid: PhantomData,
+        }
+    }
+
+    /// Inserts `key` and `value`. If an entry already existed for `key`, the
+    /// value being overwritten is returned.
+    #[inline]
+    pub fn insert(&mut self, key: K, value: V) -> Option<Field<K, V>> {
+        let index = self.fields.find_key_index(&key);
+        let replaced = self.fields.insert(key, value);
+        match index {
+            Ok(index) => {
+                let (block, start) = self.locate(index);
+                self.update_block(block, start);
+            }
+            Err(index) => self.inserted_at(index),
+        }
+        replaced
+    }
+
+    /// Returns an entry for `key`, which can be used to insert a value or to
+    /// modify an existing value.
+    ///
+    /// ```rust
+    /// use kempt::augmentedmap::Count;
+    /// use kempt::AugmentedMap;
+    ///
+    /// let mut words = AugmentedMap::<&str, u32, Count>::new();
+    /// for word in ["b", "a", "b"] {
+    ///     words.entry(word).and_modify(|count| *count += 1).or_insert(1);
+    /// }
+    /// assert_eq!(words.get(&"b"), Some(&2));
+    /// assert_eq!(words.total(), 2);
+    /// ```
+    #[inline]
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, M> {
+        match self.fields.find_key_index(&key) {
+            Ok(index) => Entry::Occupied(OccupiedEntry { map: self, index }),
+            Err(_) => Entry::Vacant(VacantEntry { map: self, key }),
+        }
+    }
+
+    /// Returns true if this map contains `key`.
+    #[inline]
+    pub fn contains<SearchFor>(&self, key: &SearchFor) -> bool
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.fields.contains(key)
+    }
+
+    /// Returns the value associated with `key`, if found.
+    #[inline]
+    pub fn get<SearchFor>(&self, key: &SearchFor) -> Option<&V>
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.fields.get(key)
+    }
+
+    /// Returns a guard providing mutable access to the value associated with
+    /// `key`, if found. The summaries are updated when the guard is dropped.
+    #[inline]
+    pub fn get_mut<SearchFor>(&mut self, key: &SearchFor) -> Option<ValueMut<'_, K, V, M>>
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        let index = self.fields.find_key_index(key).ok()?;
+        Some(ValueMut { map: self, index })
+    }
+
+    /// Returns the [`Field`] at the specified `index`, or None if the index is
+    /// outside of the bounds of this collection.
+    #[must_use]
+    #[inline]
+    pub fn field(&self, index: usize) -> Option<&Field<K, V>> {
+        self.fields.field(index)
+    }
+
+    /// Removes the value associated with `key`, if found.
+    #[inline]
+    pub fn remove<SearchFor>(&mut self, key: &SearchFor) -> Option<Field<K, V>>
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        let index = self.fields.find_key_index(key).ok()?;
+        Some(self.remove_by_index(index))
+    }
+
+    /// Removes the field at `index`.
+    ///
+    /// # Panics
+    ///
+    /// A panic will occur if `index` is greater than or equal to the map's
+    /// length.
+    #[inline]
+    pub fn remove_by_index(&mut self, index: usize) -> Field<K, V> {
+        let (block, start) = self.locate(index);
+        let removed = self.fields.remove_by_index(index);
+        self.removed_from(block, start);
+        removed
+    }
+
+    /// Returns the summary of all fields whose keys are contained in `range`.
+    #[inline]
+    pub fn aggregate<SearchFor, R>(&self, range: R) -> M::Summary
+    where
+        K: Sort<SearchFor>,
+        R: RangeBounds<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.aggregate_indices(self.fields.range_indices(range))
+    }
+
+    /// Returns the summary of all fields whose keys are less than `key`.
+    #[inline]
+    pub fn prefix<SearchFor>(&self, key: &SearchFor) -> M::Summary
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        let end = self
+            .fields
+            .find_key_index(key)
+            .unwrap_or_else(|index| index);
+        self.aggregate_indices(0..end)
+    }
+
+    /// Returns the summary of the fields whose indices are contained in
+    /// `range`. Indices beyond the end of this map are ignored.
+    #[must_use]
+    pub fn aggregate_indices(&self, range: Range<usize>) -> M::Summary {
+        let Range { start, end } = range;
+        let end = end.min(self.fields.len());
+        if start >= end {
+            return M::identity();
+        }
+
+        let (first_block, first_start) = self.locate(start);
+        let (last_block, last_start) = self.locate(end - 1);
+        if first_block == last_block {
+            return self.summarize_fields(start..end);
+        }
+        let head = self.summarize_fields(start..first_start + self.leaf(first_block).len);
+        let middle = self.summarize_blocks(first_block + 1..last_block);
+        let tail = self.summarize_fields(last_start..end);
+        M::combine(&M::combine(&head, &middle), &tail)
+    }
+
+    /// Returns the summary of all fields in this map.
+    #[must_use]
+    #[inline]
+    pub fn total(&self) -> M::Summary {
+        self.tree
+            .get(1)
+            .map_or_else(M::identity, |root| root.summary.clone())
+    }
+
+    /// Returns the index of the first field whose running summary satisfies
+    /// `predicate`, or the length of this map if no running summary satisfies
+    /// `predicate`.
+    ///
+    /// The running summary of a field is the summary of that field and every
+    /// field before it. `predicate` must be monotonic: once it returns true for
+    /// a running summary, it must return true for every following running
+    /// summary.
+    ///
+    /// ```rust
+    /// use kempt::augmentedmap::Sum;
+    /// use kempt::AugmentedMap;
+    ///
+    /// let weights = AugmentedMap::<char, u32, Sum>::from_iter([
+    ///     ('a', 3),
+    ///     ('b', 4),
+    ///     ('c', 5),
+    /// ]);
+    /// let index = weights.partition_by_aggregate(|running| *running > 6);
+    /// assert_eq!(weights.field(index).map(|field| *field.key()), Some('b'));
+    /// assert_eq!(weights.partition_by_aggregate(|running| *running > 12), 3);
+    /// ```
+    pub fn partition_by_aggregate(&self, mut predicate: impl FnMut(&M::Summary) -> bool) -> usize {
+        let width = self.tree.len() / 2;
+        if width == 0 || !predicate(&self.tree[1].summary) {
+            return self.fields.len();
+        }
+
+        let mut running = M::identity();
+        let mut start = 0;
+        let mut node = 1;
+        while node < width {
+            let left = &self.tree[node * 2];
+            let combined = M::combine(&running, &left.summary);
+            if predicate(&combined) {
+                node *= 2;
+            } else {
+                running = combined;
+                start += left.len;
+                node = node * 2 + 1;
+            }
+        }
+
+        let fields = &self.fields.fields()[start..start + self.tree[node].len];
+        for (index, field) in fields.iter().enumerate() {
+            running = M::combine(&running, &M::summarize(field.key(), &field.value));
+            if predicate(&running) {
+                return start + index;
+            }
+        }
+        self.fields.len()
+    }
+
+    /// Returns the number of fields in this map.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Returns true if this map contains no fields.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Returns an iterator over the fields in this map.
+    #[must_use]
+    #[inline]
+    pub fn iter(&self) -> map::Iter<'_, K, V> {
+        self.into_iter()
+    }
+
+    /// Returns an iterator over the fields whose keys are contained in
+    /// `range`.
+    #[inline]
+    pub fn range<SearchFor, R>(&self, range: R) -> map::Iter<'_, K, V>
+    where
+        K: Sort<SearchFor>,
+        R: RangeBounds<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.fields.range(range)
+    }
+
+    /// Returns an iterator over the keys in this map.
+    #[must_use]
+    #[inline]
+    pub fn keys(&self) -> map::Keys<'_, K, V> {
+        self.fields.keys()
+    }
+
+    /// Returns an iterator over the values in this map.
+    #[must_use]
+    #[inline]
+    pub fn values(&self) -> map::Values<'_, K, V> {
+        self.fields.values()
+    }
+
+    /// Returns the fields of this map as a [`Map`].
+    #[must_use]
+    #[inline]
+    pub fn as_map(&self) -> &Map<K, V> {
+        &self.fields
+    }
+
+    /// Returns the fields of this map as a [`Map`], discarding the summaries.
+    #[must_use]
+    #[inline]
+    pub fn into_map(self) -> Map<K, V> {
+        self.fields
+    }
+
+    /// Removes all fields from this map.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.fields.clear();
+        self.tree.clear();
+        self.blocks = 0;
+    }
+
+    fn leaf(&self, block: usize) -> &Node<M::Summary> {
+        &self.tree[self.tree.len() / 2 + block]
+    }
+
+    /// Returns the block containing the field at `index`, and the index of the
+    /// block's first field. `index` must be less than the total length of the
+    /// blocks.
+    fn locate(&self, mut index: usize) -> (usize, usize) {
+        let width = self.tree.len() / 2;
+        let mut start = 0;
+        let mut node = 1;
+        while node < width {
+            let left = self.tree[node * 2].len;
+            if index < left {
+                node *= 2;
+            } else {
+                index -= left;
+                start += left;
+                node = node * 2 + 1;
+            }
+        }
+        (node - width, start)
+    }
+
+    fn summarize_fields(&self, range: Range<usize>) -> M::Summary {
+        self.fields.fields()[range]
+            .iter()
+            .fold(M::identity(), |summary, field| {
+                M::combine(&summary, &M::summarize(field.key(), &field.value))
+            })
+    }
+
+    fn summarize_blocks(&self, blocks: Range<usize>) -> M::Summary {
+        let width = self.tree.len() / 2;
+        let mut left = M::identity();
+        let mut right = M::identity();
+        let mut start = blocks.start + width;
+        let mut end = blocks.end + width;
+        while start < end {
+            if start % 2 == 1 {
+                left = M::combine(&left, &self.tree[start].summary);
+                start += 1;
+            }
+            if end % 2 == 1 {
+                end -= 1;
+                right = M::combine(&self.tree[end].summary, &right);
+            }
+            start /= 2;
+            end /= 2;
+        }
+        M::combine(&left, &right)
+    }
+
+    /// Recomputes the summary of `block`, whose first field is at `start`,
+    /// and the lengths and summaries of its ancestors.
+    fn update_block(&mut self, block: usize, start: usize) {
+        let width = self.tree.len() / 2;
+        let mut node = width + block;
+        self.tree[node].summary = self.summarize_fields(start..start + self.tree[node].len);
+        while node > 1 {
+            node /= 2;
+            self.tree[node] = Self::combine_nodes(&self.tree[node * 2], &self.tree[node * 2 + 1]);
+        }
+    }
+
+    /// Updates the blocks after a field has been inserted at `index`.
+    fn inserted_at(&mut self, index: usize) {
+        if self.blocks == 0 {
+            self.replace_blocks(0..0, 0, 1);
+            return;
+        }
+
+        // The tree does not include the inserted field yet, so an index past
+        // the end of the tree belongs to the last block.
+        let (block, start) = if index < self.fields.len() - 1 {
+            self.locate(index)
+        } else {
+            let block = self.blocks - 1;
+            (block, index - self.leaf(block).len)
+        };
+        let len = self.leaf(block).len + 1;
+        if len > BLOCK_LEN * 2 {
+            self.replace_blocks(block..block + 1, start, len);
+        } else {
+            let width = self.tree.len() / 2;
+            self.tree[width + block].len = len;
+            self.update_block(block, start);
+        }
+    }
+
+    /// Updates the blocks after a field has been removed from `block`, whose
+    /// first field is at `start`.
+    fn removed_from(&mut self, block: usize, start: usize) {
+        let len = self.leaf(block).len - 1;
+        if len >= BLOCK_LEN / 2 || (self.blocks == 1 && len > 0) {
+            let width = self.tree.len() / 2;
+            self.tree[width + block].len = len;
+            self.update_block(block, start);
+        } else if block + 1 < self.blocks {
+            let next = self.leaf(block + 1).len;
+            self.replace_blocks(block..block + 2, start, len + next);
+        } else if block > 0 {
+            let previous = self.leaf(block - 1).len;
+            self.replace_blocks(block - 1..block + 1, start - previous, previous + len);
+        } else {
+            self.replace_blocks(0..1, 0, 0);
+        }
+    }
+
+    /// Replaces `blocks` with blocks covering the `len` fields starting at
+    /// `start`, splitting the fields in half if they are too long for a
+    /// single block. The tree above the blocks is rebuilt from the existing
+    /// block summaries.
+    fn replace_blocks(&mut self, blocks: Range<usize>, start: usize, len: usize) {
+        let mut leaves = self.leaves();
+        let pieces = match len {
+            0 => 0,
+            len if len > BLOCK_LEN * 2 => 2,
+            _ => 1,
+        };
+        leaves.splice(
+            blocks,
+            (0..pieces).map(|piece| {
+                let range = start + len * piece / pieces..start + len * (piece + 1) / pieces;
+                Node {
+                    len: range.len(),
+                    summary: self.summarize_fields(range),
+                }
+            }),
+        );
+        self.rebuild(leaves);
+    }
+
+    fn leaves(&self) -> Vec<Node<M::Summary>> {
+        let width = self.tree.len() / 2;
+        self.tree[width..width + self.blocks].to_vec()
+    }
+
+    /// Rebuilds the tree with `leaves` as its blocks.
+    fn rebuild(&mut self, leaves: Vec<Node<M::Summary>>) {
+        self.blocks = leaves.len();
+        let width = if self.blocks == 0 {
+            0
+        } else {
+            self.blocks.next_power_of_two()
+        };
+        let empty = Node {
+            len: 0,
+            summary: M::identity(),
+        };
+        self.tree.clear();
+        self.tree.resize(width, empty.clone());
+        self.tree.extend(leaves);
+        self.tree.resize(width * 2, empty);
+        for node in (1..width).rev() {
+            self.tree[node] = Self::combine_nodes(&self.tree[node * 2], &self.tree[node * 2 + 1]);
+        }
+    }
+
+    fn combine_nodes(left: &Node<M::Summary>, right: &Node<M::Summary>) -> Node<M::Summary> {
+        Node {
+            len: left.len + right.len,
+            summary: M::combine(&left.summary, &right.summary),
+        }
+    }
+}
+
+impl<K, V, M> Clone for AugmentedMap<K, V, M>
+where
+    K: Sort<K> + Clone,
+    V: Clone,
+    M: Monoid<K, V>,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            fields: self.fields.clone(),
+            tree: self.tree.clone(),
+            blocks: self.blocks,
+            monoid: PhantomData,
+        }
+    }
+}
+
+impl<K, V, M> PartialEq for AugmentedMap<K, V, M>
+where
+    K: Sort<K> + PartialEq,
+    V: PartialEq,
+    M: Monoid<K, V>,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.fields == other.fields
+    }
+}
+
+impl<K, V, M> Eq for AugmentedMap<K, V, M>
+where
+    K: Sort<K> + Eq,
+    V: Eq,
+    M: Monoid<K, V>,
+{
+}
+
+impl<K, V, M> Debug for AugmentedMap<K, V, M>
+where
+    K: Sort<K> + Debug,
+    V: Debug,
+    M: Monoid<K, V>,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_map();
+        for field in self {
+            s.entry(field.key(), &field.value);
+        }
+        s.finish()
+    }
+}
+
+impl<K, V, M> From<Map<K, V>> for AugmentedMap<K, V, M>
+where
+    K: Sort<K>,
+    M: Monoid<K, V>,
+{
+    #[inline]
+    fn from(fields: Map<K, V>) -> Self {
+        let mut map = Self {
+            fields,
+            tree: Vec::new(),
+            blocks: 0,
+            monoid: PhantomData,
+        };
+        let len = map.fields.len();
+        let leaves = (0..len)
+            .step_by(BLOCK_LEN)
+            .map(|start| {
+                let range = start..(start + BLOCK_LEN).min(len);
+                Node {
+                    len: range.len(),
+                    summary: map.summarize_fields(range),
+                }
+            })
+            .collect();
+        map.rebuild(leaves);
+        map
+    }
+}
+
+impl<'a, K, V, M> IntoIterator for &'a AugmentedMap<K, V, M>
+where
+    K: Sort<K>,
+    M: Monoid<K, V>,
+{
+    type IntoIter = map::Iter<'a, K, V>;
+    type Item = &'a Field<K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.fields.iter()
+    }
+}
+
+impl<K, V, M> IntoIterator for AugmentedMap<K, V, M>
+where
+    K: Sort<K>,
+    M: Monoid<K, V>,
+{
+    type IntoIter = map::IntoIter<K, V>;
+    type Item = Field<K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.fields.into_iter()
+    }
+}
+
+impl<K, V, M> FromIterator<(K, V)> for AugmentedMap<K, V, M>
+where
+    K: Sort<K>,
+    M: Monoid<K, V>,
+{
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::from(Map::from_iter(iter))
+    }
+}
+
+impl<K, V, M> Extend<(K, V)> for AugmentedMap<K, V, M>
+where
+    K: Sort<K>,
+    M: Monoid<K, V>,
+{
+    #[inline]
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+/// Mutable access to a value in an [`AugmentedMap`].
+///
+/// The summaries of the map are updated when this guard is dropped.
+pub struct ValueMut<'a, K, V, M>
+where
+    K: Sort<K>,
+    M: Monoid<K, V>,
+{
+    map: &'a mut AugmentedMap<K, V, M>,
+    index: usize,
+}
+
+impl<K, V, M> ValueMut<'_, K, V, M>
+where
+    K: Sort<K>,
+    M: Monoid<K, V>,
+{
+    /// Returns the key of the value being accessed.
+    #[must_use]
+    #[inline]
+    pub fn key(&self) -> &K {
+        self.map.fields.fields()[self.index].key()
+    }
+}
+
+impl<K, V, M> Deref for ValueMut<'_, K, V, M>
+where
+    K: Sort<K>,
+    M: Monoid<K, V>,
+{
+    type Target = V;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.map.fields.fields()[self.index].value
+    }
+}
+
+impl<K, V, M> DerefMut for ValueMut<'_, K, V, M>
+where
+    K: Sort<K>,
+    M: Monoid<K, V>,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.map.fields.fields_mut()[self.index].value
+    }
+}
+
+impl<K, V, M> Drop for ValueMut<'_, K, V, M>
+where
+    K: Sort<K>,
+    M: Monoid<K, V>,
+{
+    #[inline]
+    fn drop(&mut self) {
+        let (block, start) = self.map.locate(self.index);
+        self.map.update_block(block, start);
+    }
+}
+
+/// An entry in an [`AugmentedMap`].
+pub enum Entry<'a, K, V, M>
+where
+    K: Sort<K>,
+    M: Monoid<K, V>,
+{
+    /// An entry whose key is contained in the map.
+    Occupied(OccupiedEntry<'a, K, V, M>),
+    /// An entry whose key is not contained in the map.
+    Vacant(VacantEntry<'a, K, V, M>),
+}
+
+impl<'a, K, V, M> Entry<'a, K, V, M>
+where
+    K: Sort<K>,
+    M: Monoid<K, V>,
+{
+    /// Returns the key of this entry.
+    #[must_use]
+    #[inline]
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Updates the value of this entry if it is occupied.
+    #[must_use]
+    #[inline]
+    pub fn and_modify(self, update: impl FnOnce(&mut V)) -> Self {
+        match self {
+            Entry::Occupied(mut entry) => {
+                update(&mut entry.get_mut());
+                Entry::Occupied(entry)
+            }
+            vacant @ Entry::Vacant(_) => vacant,
+        }
+    }
+
+    /// Returns mutable access to the value of this entry, inserting `value` if
+    /// this entry is vacant.
+    #[inline]
+    pub fn or_insert(self, value: V) -> ValueMut<'a, K, V, M> {
+        self.or_insert_with(|| value)
+    }
+
+    /// Returns mutable access to the value of this entry, inserting the result
+    /// of `value()` if this entry is vacant.
+    #[inline]
+    pub fn or_insert_with(self, value: impl FnOnce() -> V) -> ValueMut<'a, K, V, M> {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(value()),
+        }
+    }
+
+    /// Returns mutable access to the value of this entry, inserting
+    /// `V::default()` if this entry is vacant.
+    #[inline]
+    pub fn or_default(self) -> ValueMut<'a, K, V, M>
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+}
+
+/// An entry in an [`AugmentedMap`] whose key is contained in the map.
+pub struct OccupiedEntry<'a, K, V, M>
+where
+    K: Sort<K>,
+    M: Monoid<K, V>,
+{
+    map: &'a mut AugmentedMap<K, V, M>,
+    index: usize,
+}
+
+impl<'a, K, V, M> OccupiedEntry<'a, K, V, M>
+where
+    K: Sort<K>,
+    M: Monoid<K, V>,
+{
+    /// Returns the key of this entry.
+    #[must_use]
+    #[inline]
+    pub fn key(&self) -> &K {
+        self.map.fields.fields()[self.index].key()
+    }
+
+    /// Returns the value of this entry.
+    #[must_use]
+    #[inline]
+    pub fn get(&self) -> &V {
+        &self.map.fields.fields()[self.index].value
+    }
+
+    /// Returns mutable access to the value of this entry.
+    #[inline]
+    pub fn get_mut(&mut self) -> ValueMut<'_, K, V, M> {
+        ValueMut {
+            map: self.map,
+            index: self.index,
+        }
+    }
+
+    /// Converts this entry into mutable access to its value.
+    #[must_use]
+    #[inline]
+    pub fn into_mut(self) -> ValueMut<'a, K, V, M> {
+        ValueMut {
+            map: self.map,
+            index: self.index,
+        }
+    }
+
+    /// Replaces the value of this entry with `value`, returning the previous
+    /// value.
+    #[inline]
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(&mut self.get_mut(), value)
+    }
+
+    /// Removes this entry from the map.
+    #[must_use]
+    #[inline]
+    pub fn remove(self) -> Field<K, V> {
+        self.map.remove_by_index(self.index)
+    }
+}
+
+/// An entry in an [`AugmentedMap`] whose key is not contained in the map.
+pub struct VacantEntry<'a, K, V, M>
+where
+    K: Sort<K>,
+    M: Monoid<K, V>,
+{
+    map: &'a mut AugmentedMap<K, V, M>,
+    key: K,
+}
+
+impl<'a, K, V, M> VacantEntry<'a, K, V, M>
+where
+    K: Sort<K>,
+    M: Monoid<K, V>,
+{
+    /// Returns the key of this entry.
+    #[must_use]
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Inserts `value` for this entry's key, returning mutable access to the
+    /// inserted value.
+    #[inline]
+    pub fn insert(self, value: V) -> ValueMut<'a, K, V, M> {
+        let index = match self.map.fields.find_key_index(&self.key) {
+            Ok(index) | Err(index) => index,
+        };
+        self.map.insert(self.key, value);
+        ValueMut {
+            map: self.map,
+            index,
+        }
+    }
+}
+
+#[test]
+fn aggregation() {
+    use alloc::vec::Vec;
+
+    let mut map = AugmentedMap::<u32, u64, Sum>::new();
+    assert_eq!(map.total(), 0);
+    assert_eq!(map.partition_by_aggregate(|sum| *sum > 0), 0);
+    // Insert in an order that shifts fields between blocks.
+    for key in (0..100).rev().step_by(2).chain((0..100).step_by(2)) {
+        map.insert(key, u64::from(key));
+    }
+    assert_eq!(map.len(), 100);
+
+    let naive = |range: Range<u32>| -> u64 { range.map(u64::from).sum() };
+    for start in 0..=100 {
+        for end in start..=100 {
+            assert_eq!(map.aggregate(start..end), naive(start..end));
+        }
+        assert_eq!(map.prefix(&start), naive(0..start));
+    }
+    assert_eq!(map.total(), naive(0..100));
+    assert_eq!(map.aggregate_indices(90..200), naive(90..100));
+
+    for threshold in [0, 1, 10, 1000, 4950] {
+        let expected = (0..100).position(|end| naive(0..end + 1) > threshold);
+        let index = map.partition_by_aggregate(|sum| *sum > threshold);
+        assert_eq!(Some(index).filter(|index| *index < 100), expected);
+    }
+
+    let removed = (0..100).step_by(3).collect::<Vec<_>>();
+    for key in &removed {
+        assert_eq!(map.remove(key).map(Field::into_key), Some(*key));
+    }
+    let remaining =
+        |range: Range<u32>| -> u64 { range.filter(|key| key % 3 != 0).map(u64::from).sum::<u64>() };
+    for start in 0..=100 {
+        assert_eq!(map.aggregate(start..), remaining(start..100));
+    }
+    map.clear();
+    assert_eq!(map.total(), 0);
+    assert_eq!(map.aggregate(..), 0);
+}
+
+#[test]
+fn mutation() {
+    let mut map = (0..40)
+        .map(|key| (key, key))
+        .collect::<AugmentedMap<u32, u32, Max>>();
+    assert_eq!(map.aggregate(..20), Some(19));
+    *map.get_mut(&5).unwrap() = 100;
+    assert_eq!(map.aggregate(..20), Some(100));
+    assert_eq!(map.aggregate(20..), Some(39));
+
+    assert_eq!(map.insert(30, 200).map(Field::into_parts), Some((30, 30)));
+    assert_eq!(map.aggregate(20..), Some(200));
+
+    *map.entry(31).and_modify(|value| *value = 300).or_insert(0) += 1;
+    assert_eq!(map.total(), Some(301));
+    *map.entry(50).or_default() += 400;
+    assert_eq!(map.aggregate(40..), Some(400));
+    match map.entry(50) {
+        Entry::Occupied(mut entry) => {
+            assert_eq!(entry.insert(7), 400);
+            assert_eq!(*entry.get(), 7);
+            assert_eq!(entry.remove().into_parts(), (50, 7));
+        }
+        Entry::Vacant(_) => unreachable!("50 was inserted"),
+    }
+    assert_eq!(map.aggregate(40..), None);
+    assert_eq!(map.len(), 40);
+
+    let min = AugmentedMap::<u32, u32, Min>::from(map.clone().into_map());
+    assert_eq!(min.aggregate(1..), Some(1));
+    let count = AugmentedMap::<u32, u32, Count>::from(map.into_map());
+    assert_eq!(count.aggregate(10..=19), 10);
+}
+
+#[test]
+fn rebalancing() {
+    fn check(map: &AugmentedMap<u32, u64, Sum>) {
+        let leaves = map.leaves();
+        assert_eq!(leaves.iter().map(|leaf| leaf.len).sum::<usize>(), map.len());
+        for leaf in &leaves {
+            assert!(leaf.len <= BLOCK_LEN * 2);
+            assert!(leaf.len >= BLOCK_LEN / 2 || leaves.len() == 1);
+        }
+        let expected = map.values().sum::<u64>();
+        assert_eq!(map.total(), expected);
+        assert_eq!(map.aggregate_indices(0..map.len()), expected);
+    }
+
+    let mut map = AugmentedMap::<u32, u64, Sum>::new();
+    // Insert into the middle of the map so that the same blocks keep
+    // splitting.
+    for key in (0..250).chain((250..500).rev()) {
+        map.insert(key, u64::from(key));
+        check(&map);
+    }
+    for key in (0..500).filter(|key| key % 5 != 0) {
+        map.remove(&key);
+        check(&map);
+    }
+    assert_eq!(map.aggregate(100..200), (100..200).step_by(5).sum());
+    for key in (0..500).step_by(5).rev() {
+        map.remove(&key);
+        check(&map);
+    }
+    assert!(map.is_empty());
+    assert!(map.tree.is_empty());
+}
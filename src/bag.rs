@@ -0,0 +1,582 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Debug};
+use core::iter::FusedIterator;
+use core::ops::{BitAnd, BitOr, Sub};
+
+use crate::map::{self, Field, Unioned};
+use crate::{Map, Sort};
+
+/// An ordered collection that counts the number of times each unique `T` has
+/// been added.
+///
+/// This type is also known as a multiset, and is similar to using a
+/// `Map<T, usize>` to count occurrences. Members whose count reaches zero are
+/// removed from the collection.
+///
+/// ```rust
+/// use kempt::Bag;
+///
+/// let mut bag = Bag::new();
+/// bag.add("apple", 2);
+/// bag.add("pear", 1);
+/// bag.add("apple", 1);
+/// assert_eq!(bag.count(&"apple"), 3);
+/// assert_eq!(bag.total(), 4);
+/// assert_eq!(bag.most_common(1), [(&"apple", 3)]);
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Bag<T>
+where
+    T: Sort<T>,
+{
+    counts: Map<T, usize>,
+    total: usize,
+}
+
+impl<T> Default for Bag<T>
+where
+    T: Sort<T>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Bag<T>
+where
+    T: Sort<T>,
+{
+    /// Returns an empty bag.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            counts: Map::new(),
+            total: 0,
+        }
+    }
+
+    /// Adds `n` occurrences of `value` to this bag, returning the new count of
+    /// `value`.
+    ///
+    /// If `n` is zero, the bag is not modified.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the bag's total would overflow a `usize`. The
+    /// bag is not modified when this happens.
+    #[inline]
+    pub fn add(&mut self, value: T, n: usize) -> usize {
+        if n == 0 {
+            return self.count(&value);
+        }
+        // Each count is bounded by the total, so checking the total is enough
+        // to keep the count from overflowing.
+        let total = add_counts(self.total, n);
+        let count = self.counts.get_or_insert_with(value, || 0);
+        *count += n;
+        self.total = total;
+        *count
+    }
+
+    /// Removes up to `n` occurrences of `value` from this bag, returning the
+    /// number of occurrences removed.
+    ///
+    /// If the count of `value` reaches zero, `value` is removed from the bag.
+    ///
+    /// ```rust
+    /// use kempt::Bag;
+    ///
+    /// let mut bag = Bag::from_iter(["a", "a", "b"]);
+    /// assert_eq!(bag.remove(&"a", 1), 1);
+    /// assert_eq!(bag.remove(&"a", 5), 1);
+    /// assert!(!bag.contains(&"a"));
+    /// ```
+    #[inline]
+    pub fn remove<SearchFor>(&mut self, value: &SearchFor, n: usize) -> usize
+    where
+        T: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        let Some(count) = self.counts.get_mut(value) else {
+            return 0;
+        };
+        let removed = n.min(*count);
+        *count -= removed;
+        if *count == 0 {
+            self.counts.remove(value);
+        }
+        self.total -= removed;
+        removed
+    }
+
+    /// Removes every occurrence of `value` from this bag, returning the number
+    /// of occurrences removed.
+    #[inline]
+    pub fn remove_all<SearchFor>(&mut self, value: &SearchFor) -> usize
+    where
+        T: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        let removed = self.counts.remove(value).map_or(0, |field| field.value);
+        self.total -= removed;
+        removed
+    }
+
+    /// Returns the number of occurrences of `value` in this bag.
+    #[inline]
+    pub fn count<SearchFor>(&self, value: &SearchFor) -> usize
+    where
+        T: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.counts.get(value).copied().unwrap_or(0)
+    }
+
+    /// Returns true if this bag contains at least one occurrence of `value`.
+    #[inline]
+    pub fn contains<SearchFor>(&self, value: &SearchFor) -> bool
+    where
+        T: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.counts.contains(value)
+    }
+
+    /// Returns the total number of occurrences of all members in this bag.
+    #[must_use]
+    #[inline]
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Returns the number of unique members in this bag.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Returns true if there are no members in this bag.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Returns an iterator over the unique members in this bag and their
+    /// counts.
+    ///
+    /// This iterator is guaranteed to return results in the sort order of the
+    /// `T` type.
+    #[must_use]
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.into_iter()
+    }
+
+    /// Returns up to `n` members with the highest counts, ordered from the
+    /// highest count to the lowest.
+    ///
+    /// Members with equal counts are returned in the sort order of the `T`
+    /// type.
+    ///
+    /// Rather than sorting every member, the `n` members are selected in `O(m)`
+    /// average time, where `m` is the number of unique members, and then sorted
+    /// in `O(n log n)` time.
+    #[must_use]
+    pub fn most_common(&self, n: usize) -> Vec<(&T, usize)> {
+        let by_count = |(a, a_count): &(&T, usize), (b, b_count): &(&T, usize)| {
+            b_count.cmp(a_count).then_with(|| a.compare(b))
+        };
+        let mut members = self.iter().collect::<Vec<_>>();
+        if n < members.len() {
+            if n == 0 {
+                return Vec::new();
+            }
+            members.select_nth_unstable_by(n - 1, by_count);
+            members.truncate(n);
+        }
+        members.sort_unstable_by(by_count);
+        members
+    }
+
+    /// Returns a new bag containing each member found in either `self` or
+    /// `other`, with the larger of the two counts.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_sorted_counts(
+            self.counts
+                .union(&other.counts)
+                .map(|unioned| match unioned {
+                    Unioned::Left { key, value } | Unioned::Right { key, value } => (key, *value),
+                    Unioned::Both { key, left, right } => (key, *left.max(right)),
+                }),
+        )
+    }
+
+    /// Returns a new bag containing each member found in both `self` and
+    /// `other`, with the smaller of the two counts.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_sorted_counts(
+            self.counts
+                .intersection(&other.counts)
+                .map(|(key, left, right)| (key, *left.min(right))),
+        )
+    }
+
+    /// Returns a new bag containing each member found in either `self` or
+    /// `other`, with the sum of the two counts.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the resulting bag's total would overflow a
+    /// `usize`.
+    #[must_use]
+    pub fn sum(&self, other: &Self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_sorted_counts(
+            self.counts
+                .union(&other.counts)
+                .map(|unioned| match unioned {
+                    Unioned::Left { key, value } | Unioned::Right { key, value } => (key, *value),
+                    Unioned::Both { key, left, right } => (key, add_counts(*left, *right)),
+                }),
+        )
+    }
+
+    /// Returns a new bag containing each member of `self` with its count
+    /// reduced by its count in `other`. Members whose count reaches zero are
+    /// not included.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_sorted_counts(self.counts.union(&other.counts).filter_map(
+            |unioned| match unioned {
+                Unioned::Left { key, value } => Some((key, *value)),
+                Unioned::Both { key, left, right } => Some((key, left.saturating_sub(*right))),
+                Unioned::Right { .. } => None,
+            },
+        ))
+    }
+
+    /// Clears the contents of this collection.
+    ///
+    /// This does not return any allocated memory to the OS.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.counts.clear();
+        self.total = 0;
+    }
+
+    /// Returns a bag containing the members and counts returned from `counts`,
+    /// which must be returned in sort order with no duplicates. Members with a
+    /// count of zero are skipped.
+    ///
+    /// Panics if the total of the counts overflows a `usize`.
+    fn from_sorted_counts<'a>(counts: impl Iterator<Item = (&'a T, usize)>) -> Self
+    where
+        T: Clone + 'a,
+    {
+        let mut total = 0;
+        let fields = counts
+            .filter(|(_, count)| *count > 0)
+            .map(|(member, count)| {
+                total = add_counts(total, count);
+                Field::new(member.clone(), count)
+            })
+            .collect();
+        Self {
+            counts: Map::from_sorted_fields(fields),
+            total,
+        }
+    }
+}
+
+impl<T> BitOr<&Bag<T>> for &Bag<T>
+where
+    T: Sort<T> + Clone,
+{
+    type Output = Bag<T>;
+
+    /// Returns the [union](Bag::union) of `self` and `rhs`.
+    #[inline]
+    fn bitor(self, rhs: &Bag<T>) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+impl<T> BitAnd<&Bag<T>> for &Bag<T>
+where
+    T: Sort<T> + Clone,
+{
+    type Output = Bag<T>;
+
+    /// Returns the [intersection](Bag::intersection) of `self` and `rhs`.
+    #[inline]
+    fn bitand(self, rhs: &Bag<T>) -> Self::Output {
+        self.intersection(rhs)
+    }
+}
+
+impl<T> Sub<&Bag<T>> for &Bag<T>
+where
+    T: Sort<T> + Clone,
+{
+    type Output = Bag<T>;
+
+    /// Returns the [difference](Bag::difference) of `self` and `rhs`.
+    #[inline]
+    fn sub(self, rhs: &Bag<T>) -> Self::Output {
+        self.difference(rhs)
+    }
+}
+
+impl<T> Debug for Bag<T>
+where
+    T: Sort<T> + Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_map();
+        for (member, count) in self {
+            s.entry(member, &count);
+        }
+        s.finish()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Bag<T>
+where
+    T: Sort<T>,
+{
+    type IntoIter = Iter<'a, T>;
+    type Item = (&'a T, usize);
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        Iter(self.counts.iter())
+    }
+}
+
+impl<T> FromIterator<T> for Bag<T>
+where
+    T: Sort<T>,
+{
+    /// Collects `iter` into a bag, sorting the members once and counting runs
+    /// of equal members rather than adding each member individually.
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut members = iter.into_iter().collect::<Vec<_>>();
+        // The sort is stable, which keeps the first occurrence of each member
+        // as the stored member, matching the behavior of `Bag::add`.
+        members.sort_by(Sort::compare);
+
+        let total = members.len();
+        let mut counts = Vec::<Field<T, usize>>::new();
+        for member in members {
+            match counts.last_mut() {
+                Some(field) if field.key().compare(&member).is_eq() => field.value += 1,
+                _ => counts.push(Field::new(member, 1)),
+            }
+        }
+        Self {
+            counts: Map::from_sorted_fields(counts),
+            total,
+        }
+    }
+}
+
+/// Adds two counts or totals, panicking if the result overflows a `usize`.
+fn add_counts(a: usize, b: usize) -> usize {
+    a.checked_add(b).expect("bag count overflowed usize")
+}
+
+impl<T> Extend<T> for Bag<T>
+where
+    T: Sort<T>,
+{
+    /// Counts the members of `iter` in a single batch, and then merges the
+    /// batch into this bag in a single pass.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the bag's total would overflow a `usize`. The
+    /// bag is not modified when this happens.
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let batch = Self::from_iter(iter);
+        if self.is_empty() {
+            *self = batch;
+        } else {
+            // Each count is bounded by the total, so checking the total is
+            // enough to keep the merged counts from overflowing.
+            let total = add_counts(self.total, batch.total);
+            self.counts
+                .merge_from(batch.counts, |_, count, added| *count += added);
+            self.total = total;
+        }
+    }
+}
+
+/// An iterator over the members of a [`Bag`] and their counts.
+///
+/// This iterator is guaranteed to return results in the sort order of the `T`
+/// type.
+pub struct Iter<'a, T>(map::Iter<'a, T, usize>);
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (&'a T, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|field| (field.key(), field.value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|field| (field.key(), field.value))
+    }
+}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+#[test]
+fn basics() {
+    let mut bag = Bag::default();
+    assert!(bag.is_empty());
+    assert_eq!(bag.add('a', 2), 2);
+    assert_eq!(bag.add('b', 0), 0);
+    assert!(!bag.contains(&'b'));
+    assert_eq!(bag.add('b', 1), 1);
+    assert_eq!(bag.add('a', 1), 3);
+    assert_eq!(bag.add('a', 0), 3);
+    assert_eq!(bag.len(), 2);
+    assert_eq!(bag.total(), 4);
+    assert_eq!(bag.count(&'a'), 3);
+    assert_eq!(bag.count(&'z'), 0);
+
+    assert_eq!(bag.remove(&'a', 2), 2);
+    assert_eq!(bag.count(&'a'), 1);
+    assert_eq!(bag.total(), 2);
+    assert_eq!(bag.remove(&'a', 2), 1);
+    assert!(!bag.contains(&'a'));
+    assert_eq!(bag.remove(&'a', 2), 0);
+    assert_eq!(bag.total(), 1);
+
+    bag.add('c', 5);
+    assert_eq!(bag.remove_all(&'c'), 5);
+    assert_eq!(bag.remove_all(&'c'), 0);
+    assert_eq!(bag.total(), 1);
+    assert!(bag.iter().eq([(&'b', 1)]));
+    bag.clear();
+    assert!(bag.is_empty());
+    assert_eq!(bag.total(), 0);
+}
+
+#[test]
+fn collect_and_extend() {
+    let mut bag = "abracadabra".chars().collect::<Bag<_>>();
+    assert!(bag
+        .iter()
+        .eq([(&'a', 5), (&'b', 2), (&'c', 1), (&'d', 1), (&'r', 2)]));
+    assert_eq!(bag.total(), 11);
+
+    bag.extend("bazaar".chars());
+    assert!(bag.iter().eq([
+        (&'a', 8),
+        (&'b', 3),
+        (&'c', 1),
+        (&'d', 1),
+        (&'r', 3),
+        (&'z', 1)
+    ]));
+    assert_eq!(bag.total(), 17);
+
+    let mut empty = Bag::new();
+    empty.extend([3, 1, 3]);
+    assert!(empty.iter().eq([(&1, 1), (&3, 2)]));
+    assert_eq!(empty.total(), 3);
+}
+
+#[test]
+fn most_common() {
+    use alloc::format;
+
+    let bag = "mississippi".chars().collect::<Bag<_>>();
+    assert_eq!(bag.total(), 11);
+    assert_eq!(bag.most_common(1), [(&'i', 4)]);
+    assert_eq!(bag.most_common(2), [(&'i', 4), (&'s', 4)]);
+    assert_eq!(bag.most_common(3), [(&'i', 4), (&'s', 4), (&'p', 2)]);
+    assert_eq!(
+        bag.most_common(10),
+        [(&'i', 4), (&'s', 4), (&'p', 2), (&'m', 1)]
+    );
+    assert!(bag.most_common(0).is_empty());
+    assert!(bag
+        .iter()
+        .rev()
+        .map(|(member, _)| *member)
+        .eq(['s', 'p', 'm', 'i']));
+    assert_eq!(format!("{bag:?}"), "{'i': 4, 'm': 1, 'p': 2, 's': 4}");
+}
+
+#[test]
+fn arithmetic() {
+    let a = "aaabbc".chars().collect::<Bag<_>>();
+    let b = "abbbd".chars().collect::<Bag<_>>();
+
+    let union = &a | &b;
+    assert!(union
+        .iter()
+        .eq([(&'a', 3), (&'b', 3), (&'c', 1), (&'d', 1)]));
+    assert_eq!(union.total(), 8);
+
+    let intersection = &a & &b;
+    assert!(intersection.iter().eq([(&'a', 1), (&'b', 2)]));
+    assert_eq!(intersection.total(), 3);
+
+    let sum = a.sum(&b);
+    assert!(sum.iter().eq([(&'a', 4), (&'b', 5), (&'c', 1), (&'d', 1)]));
+    assert_eq!(sum.total(), 11);
+
+    let difference = &a - &b;
+    assert!(difference.iter().eq([(&'a', 2), (&'c', 1)]));
+    assert_eq!(difference.total(), 3);
+    assert!((&b - &a).iter().eq([(&'b', 1), (&'d', 1)]));
+    assert!((&a - &a).is_empty());
+}
+
+#[test]
+#[should_panic = "bag count overflowed usize"]
+fn add_overflow() {
+    let mut bag = Bag::new();
+    bag.add('a', usize::MAX);
+    bag.add('b', 1);
+}
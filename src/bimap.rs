@@ -0,0 +1,444 @@
+use core::cmp::Ordering;
+use core::fmt::{self, Debug};
+use core::iter::FusedIterator;
+
+use crate::map;
+use crate::{Map, Sort};
+
+/// An ordered one-to-one mapping between `L` and `R` values.
+///
+/// Each left value is associated with exactly one right value, and each right
+/// value is associated with exactly one left value. Pairs can be looked up,
+/// removed, and iterated in the sort order of either side.
+///
+/// The pairs are stored in two [`Map`]s: one sorted by the left values, and one
+/// sorted by the right values. Because of this, both `L` and `R` must implement
+/// [`Clone`] to insert pairs.
+///
+/// ```rust
+/// use kempt::BiMap;
+///
+/// let mut ids = BiMap::new();
+/// ids.insert(1, String::from("alice"));
+/// ids.insert(2, String::from("bob"));
+/// assert_eq!(ids.get_by_left(&1).map(String::as_str), Some("alice"));
+/// assert_eq!(ids.get_by_right("bob"), Some(&2));
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct BiMap<L, R>
+where
+    L: Sort<L>,
+    R: Sort<R>,
+{
+    by_left: Map<L, R>,
+    by_right: Map<R, L>,
+}
+
+impl<L, R> Default for BiMap<L, R>
+where
+    L: Sort<L>,
+    R: Sort<R>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<L, R> BiMap<L, R>
+where
+    L: Sort<L>,
+    R: Sort<R>,
+{
+    /// Returns an empty map.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            by_left: Map::new(),
+            by_right: Map::new(),
+        }
+    }
+
+    /// Returns an empty map with enough allocated memory to store `capacity`
+    /// pairs without reallocating.
+    #[must_use]
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            by_left: Map::with_capacity(capacity),
+            by_right: Map::with_capacity(capacity),
+        }
+    }
+
+    /// Inserts the pair `left` and `right`, removing any existing pairs that
+    /// contain either value.
+    ///
+    /// The returned [`Overwritten`] describes which pairs were displaced.
+    ///
+    /// ```rust
+    /// use kempt::bimap::Overwritten;
+    /// use kempt::BiMap;
+    ///
+    /// let mut map = BiMap::new();
+    /// assert_eq!(map.insert(1, 'a'), Overwritten::Neither);
+    /// assert_eq!(map.insert(2, 'b'), Overwritten::Neither);
+    /// assert_eq!(map.insert(1, 'a'), Overwritten::Pair(1, 'a'));
+    /// assert_eq!(map.insert(1, 'b'), Overwritten::Both((1, 'a'), (2, 'b')));
+    /// assert_eq!(map.len(), 1);
+    /// ```
+    pub fn insert(&mut self, left: L, right: R) -> Overwritten<L, R>
+    where
+        L: Clone,
+        R: Clone,
+    {
+        let by_left = self.remove_by_left(&left);
+        let by_right = self.remove_by_right(&right);
+        let overwritten = match (by_left, by_right) {
+            (None, None) => Overwritten::Neither,
+            (Some((old_left, old_right)), None) if old_right.compare(&right) == Ordering::Equal => {
+                Overwritten::Pair(old_left, old_right)
+            }
+            (Some((old_left, old_right)), None) => Overwritten::Left(old_left, old_right),
+            (None, Some((old_left, old_right))) => Overwritten::Right(old_left, old_right),
+            (Some(by_left), Some(by_right)) => Overwritten::Both(by_left, by_right),
+        };
+
+        self.by_left.insert(left.clone(), right.clone());
+        self.by_right.insert(right, left);
+        overwritten
+    }
+
+    /// Returns the right value associated with `left`, if found.
+    #[inline]
+    pub fn get_by_left<SearchFor>(&self, left: &SearchFor) -> Option<&R>
+    where
+        L: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.by_left.get(left)
+    }
+
+    /// Returns the left value associated with `right`, if found.
+    #[inline]
+    pub fn get_by_right<SearchFor>(&self, right: &SearchFor) -> Option<&L>
+    where
+        R: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.by_right.get(right)
+    }
+
+    /// Returns true if this map contains a pair with a left value matching
+    /// `left`.
+    #[inline]
+    pub fn contains_left<SearchFor>(&self, left: &SearchFor) -> bool
+    where
+        L: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.by_left.contains(left)
+    }
+
+    /// Returns true if this map contains a pair with a right value matching
+    /// `right`.
+    #[inline]
+    pub fn contains_right<SearchFor>(&self, right: &SearchFor) -> bool
+    where
+        R: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.by_right.contains(right)
+    }
+
+    /// Removes the pair whose left value matches `left`, returning the removed
+    /// pair if found.
+    #[inline]
+    pub fn remove_by_left<SearchFor>(&mut self, left: &SearchFor) -> Option<(L, R)>
+    where
+        L: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        let (left, right) = self.by_left.remove(left)?.into_parts();
+        self.by_right.remove(&right);
+        Some((left, right))
+    }
+
+    /// Removes the pair whose right value matches `right`, returning the
+    /// removed pair if found.
+    #[inline]
+    pub fn remove_by_right<SearchFor>(&mut self, right: &SearchFor) -> Option<(L, R)>
+    where
+        R: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        let (right, left) = self.by_right.remove(right)?.into_parts();
+        self.by_left.remove(&left);
+        Some((left, right))
+    }
+
+    /// Returns the number of pairs in this map.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.by_left.len()
+    }
+
+    /// Returns true if there are no pairs in this map.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.by_left.is_empty()
+    }
+
+    /// Returns an iterator over the pairs in this map, in the sort order of
+    /// the left values.
+    #[must_use]
+    #[inline]
+    pub fn iter_by_left(&self) -> IterByLeft<'_, L, R> {
+        IterByLeft(self.by_left.iter())
+    }
+
+    /// Returns an iterator over the pairs in this map, in the sort order of
+    /// the left values.
+    ///
+    /// This is equivalent to [`iter_by_left()`](Self::iter_by_left).
+    #[must_use]
+    #[inline]
+    pub fn iter(&self) -> IterByLeft<'_, L, R> {
+        self.iter_by_left()
+    }
+
+    /// Returns an iterator over the pairs in this map, in the sort order of
+    /// the right values.
+    ///
+    /// ```rust
+    /// use kempt::BiMap;
+    ///
+    /// let map = BiMap::from_iter([(1, 'b'), (2, 'a')]);
+    /// assert!(map.iter_by_left().eq([(&1, &'b'), (&2, &'a')]));
+    /// assert!(map.iter_by_right().eq([(&2, &'a'), (&1, &'b')]));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn iter_by_right(&self) -> IterByRight<'_, L, R> {
+        IterByRight(self.by_right.iter())
+    }
+
+    /// Clears the contents of this collection.
+    ///
+    /// This does not return any allocated memory to the OS.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.by_left.clear();
+        self.by_right.clear();
+    }
+}
+
+impl<L, R> Debug for BiMap<L, R>
+where
+    L: Sort<L> + Debug,
+    R: Sort<R> + Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_map();
+        for (left, right) in self.iter_by_left() {
+            s.entry(left, right);
+        }
+        s.finish()
+    }
+}
+
+impl<'a, L, R> IntoIterator for &'a BiMap<L, R>
+where
+    L: Sort<L>,
+    R: Sort<R>,
+{
+    type IntoIter = IterByLeft<'a, L, R>;
+    type Item = (&'a L, &'a R);
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_by_left()
+    }
+}
+
+impl<L, R> FromIterator<(L, R)> for BiMap<L, R>
+where
+    L: Sort<L> + Clone,
+    R: Sort<R> + Clone,
+{
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (L, R)>>(iter: T) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<L, R> Extend<(L, R)> for BiMap<L, R>
+where
+    L: Sort<L> + Clone,
+    R: Sort<R> + Clone,
+{
+    #[inline]
+    fn extend<T: IntoIterator<Item = (L, R)>>(&mut self, iter: T) {
+        for (left, right) in iter {
+            self.insert(left, right);
+        }
+    }
+}
+
+/// The pairs removed from a [`BiMap`] when inserting a new pair.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Overwritten<L, R> {
+    /// No existing pairs contained either value.
+    Neither,
+    /// The inserted pair was already contained in the map.
+    Pair(L, R),
+    /// An existing pair contained the same left value.
+    Left(L, R),
+    /// An existing pair contained the same right value.
+    Right(L, R),
+    /// One existing pair contained the same left value, and another existing
+    /// pair contained the same right value.
+    Both((L, R), (L, R)),
+}
+
+/// An iterator over the pairs in a [`BiMap`], in the sort order of the left
+/// values.
+pub struct IterByLeft<'a, L, R>(map::Iter<'a, L, R>);
+
+impl<'a, L, R> Iterator for IterByLeft<'a, L, R> {
+    type Item = (&'a L, &'a R);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|field| (field.key(), &field.value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<L, R> ExactSizeIterator for IterByLeft<'_, L, R> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<L, R> DoubleEndedIterator for IterByLeft<'_, L, R> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|field| (field.key(), &field.value))
+    }
+}
+
+impl<L, R> FusedIterator for IterByLeft<'_, L, R> {}
+
+/// An iterator over the pairs in a [`BiMap`], in the sort order of the right
+/// values.
+pub struct IterByRight<'a, L, R>(map::Iter<'a, R, L>);
+
+impl<'a, L, R> Iterator for IterByRight<'a, L, R> {
+    type Item = (&'a L, &'a R);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|field| (&field.value, field.key()))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<L, R> ExactSizeIterator for IterByRight<'_, L, R> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<L, R> DoubleEndedIterator for IterByRight<'_, L, R> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|field| (&field.value, field.key()))
+    }
+}
+
+impl<L, R> FusedIterator for IterByRight<'_, L, R> {}
+
+#[test]
+fn basics() {
+    use alloc::format;
+    use alloc::string::String;
+
+    let mut map = BiMap::default();
+    assert!(map.is_empty());
+    assert_eq!(map.insert(String::from("a"), 1), Overwritten::Neither);
+    assert_eq!(map.insert(String::from("b"), 2), Overwritten::Neither);
+    assert_eq!(map.len(), 2);
+
+    // Borrowed lookups
+    assert_eq!(map.get_by_left("a"), Some(&1));
+    assert_eq!(map.get_by_right(&2).map(String::as_str), Some("b"));
+    assert!(map.contains_left("b"));
+    assert!(!map.contains_left("c"));
+    assert!(map.contains_right(&1));
+    assert!(!map.contains_right(&3));
+
+    assert_eq!(
+        map.insert(String::from("a"), 3),
+        Overwritten::Left(String::from("a"), 1)
+    );
+    assert_eq!(
+        map.insert(String::from("c"), 3),
+        Overwritten::Right(String::from("a"), 3)
+    );
+    assert_eq!(
+        map.insert(String::from("c"), 3),
+        Overwritten::Pair(String::from("c"), 3)
+    );
+    assert_eq!(
+        map.insert(String::from("b"), 3),
+        Overwritten::Both((String::from("b"), 2), (String::from("c"), 3))
+    );
+    assert_eq!(map.len(), 1);
+    assert_eq!(format!("{map:?}"), r#"{"b": 3}"#);
+
+    map.extend([(String::from("z"), 0), (String::from("y"), 10)]);
+    assert_eq!(map.remove_by_left("z"), Some((String::from("z"), 0)));
+    assert_eq!(map.remove_by_left("z"), None);
+    assert!(!map.contains_right(&0));
+    assert_eq!(map.remove_by_right(&10), Some((String::from("y"), 10)));
+    assert_eq!(map.remove_by_right(&10), None);
+    assert!(!map.contains_left("y"));
+    assert_eq!(map.len(), 1);
+    map.clear();
+    assert!(map.is_empty());
+}
+
+#[test]
+fn iteration() {
+    let map = BiMap::from_iter([(3, 'a'), (1, 'c'), (2, 'b')]);
+    assert!(map.iter_by_left().eq([(&1, &'c'), (&2, &'b'), (&3, &'a')]));
+    assert!(map.iter_by_right().eq([(&3, &'a'), (&2, &'b'), (&1, &'c')]));
+    assert!(map
+        .iter_by_left()
+        .rev()
+        .map(|(left, _)| *left)
+        .eq([3, 2, 1]));
+    assert!(map
+        .iter_by_right()
+        .rev()
+        .map(|(_, right)| *right)
+        .eq(['c', 'b', 'a']));
+    assert_eq!(map.iter_by_right().len(), 3);
+    assert_eq!(map.iter().len(), 3);
+}
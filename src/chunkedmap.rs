@@ -0,0 +1,850 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::{self, Debug};
+use core::iter::FusedIterator;
+use core::mem;
+use core::ops::{Bound, Deref, DerefMut, RangeBounds};
+use core::slice;
+
+use crate::map::Field;
+use crate::{Map, Sort};
+
+/// The number of fields a chunk can contain before it is split in half.
+const MAX_CHUNK_LEN: usize = 512;
+
+/// An ordered map that stores its fields in a sorted list of bounded chunks.
+///
+/// Inserting into a [`Map`] shifts every field after the inserted field, which
+/// becomes expensive once a map contains thousands of fields. This type stores
+/// its fields in chunks of at most 512 fields, each of which is a [`Map`]
+/// searched using the same hybrid binary search and sequential scan algorithm.
+/// Inserting or removing a field only shifts the fields within a single
+/// chunk.
+///
+/// Chunks are split in half once they exceed their maximum length, and small
+/// chunks are merged with a neighboring chunk when their fields fit within a
+/// single chunk.
+///
+/// Locating a field by its index requires visiting each chunk before it, so
+/// [`field()`](Self::field) is `O(n / 512)` rather than `O(1)`.
+///
+/// ```rust
+/// use kempt::ChunkedMap;
+///
+/// let mut map = ChunkedMap::new();
+/// for n in (0..10_000).rev() {
+///     map.insert(n, n * 2);
+/// }
+/// assert_eq!(map.get(&42), Some(&84));
+/// assert_eq!(map.field(5_000).map(|field| *field.key()), Some(5_000));
+/// assert!(map.range(10..13).map(|field| field.value).eq([20, 22, 24]));
+/// ```
+#[derive(Clone)]
+pub struct ChunkedMap<K, V>
+where
+    K: Sort<K>,
+{
+    /// The chunks of this map, none of which are empty.
+    chunks: Vec<Map<K, V>>,
+    len: usize,
+}
+
+impl<K, V> Default for ChunkedMap<K, V>
+where
+    K: Sort<K>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> ChunkedMap<K, V>
+where
+    K: Sort<K>,
+{
+    /// Returns an empty map.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            chunks: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Inserts `key` and `value`. If an entry already existed for `key`, the
+    /// field being overwritten is returned.
+    #[inline]
+    pub fn insert(&mut self, key: K, value: V) -> Option<Field<K, V>> {
+        let (chunk, found) = self.locate(&key);
+        let field = Field::new(key, value);
+        match found {
+            Ok(index) => Some(mem::replace(
+                &mut self.chunks[chunk].fields_mut()[index],
+                field,
+            )),
+            Err(index) => {
+                self.insert_at(chunk, index, field);
+                None
+            }
+        }
+    }
+
+    /// Returns an [`Entry`] for `key`.
+    ///
+    /// ```rust
+    /// use kempt::ChunkedMap;
+    ///
+    /// let mut counts = ChunkedMap::new();
+    /// for word in ["a", "b", "a"] {
+    ///     *counts.entry(word).or_default() += 1;
+    /// }
+    /// assert_eq!(counts.get(&"a"), Some(&2));
+    /// ```
+    #[inline]
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        match self.locate(&key) {
+            (chunk, Ok(index)) => Entry::Occupied(OccupiedEntry {
+                map: self,
+                chunk,
+                index,
+            }),
+            (chunk, Err(index)) => Entry::Vacant(VacantEntry {
+                map: self,
+                key,
+                chunk,
+                index,
+            }),
+        }
+    }
+
+    /// Returns true if this map contains `key`.
+    #[inline]
+    pub fn contains<SearchFor>(&self, key: &SearchFor) -> bool
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.get_field(key).is_some()
+    }
+
+    /// Returns the value associated with `key`, if found.
+    #[inline]
+    pub fn get<SearchFor>(&self, key: &SearchFor) -> Option<&V>
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.get_field(key).map(|field| &field.value)
+    }
+
+    /// Returns a mutable reference to the value associated with `key`, if
+    /// found.
+    #[inline]
+    pub fn get_mut<SearchFor>(&mut self, key: &SearchFor) -> Option<&mut V>
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        let (chunk, found) = self.locate(key);
+        let index = found.ok()?;
+        Some(&mut self.chunks[chunk].fields_mut()[index].value)
+    }
+
+    /// Returns the field associated with `key`, if found.
+    #[inline]
+    pub fn get_field<SearchFor>(&self, key: &SearchFor) -> Option<&Field<K, V>>
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        let (chunk, found) = self.locate(key);
+        let index = found.ok()?;
+        self.chunks[chunk].field(index)
+    }
+
+    /// Returns the [`Field`] at the specified `index`, or None if the index is
+    /// outside of the bounds of this collection.
+    #[must_use]
+    #[inline]
+    pub fn field(&self, index: usize) -> Option<&Field<K, V>> {
+        let (chunk, index) = self.chunk_of_index(index)?;
+        self.chunks[chunk].field(index)
+    }
+
+    /// Returns a mutable reference to the [`Field`] at the specified `index`,
+    /// or None if the index is outside of the bounds of this collection.
+    #[must_use]
+    #[inline]
+    pub fn field_mut(&mut self, index: usize) -> Option<&mut Field<K, V>> {
+        let (chunk, index) = self.chunk_of_index(index)?;
+        self.chunks[chunk].field_mut(index)
+    }
+
+    /// Removes the value associated with `key`, if found.
+    #[inline]
+    pub fn remove<SearchFor>(&mut self, key: &SearchFor) -> Option<Field<K, V>>
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        let (chunk, found) = self.locate(key);
+        Some(self.remove_at(chunk, found.ok()?))
+    }
+
+    /// Removes the field at `index`.
+    ///
+    /// # Panics
+    ///
+    /// A panic will occur if `index` is greater than or equal to the map's
+    /// length.
+    #[inline]
+    pub fn remove_by_index(&mut self, index: usize) -> Field<K, V> {
+        let (chunk, index) = self.chunk_of_index(index).expect("index out of bounds");
+        self.remove_at(chunk, index)
+    }
+
+    /// Returns the number of fields in this map.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if this map contains no fields.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator over the fields in this map.
+    #[must_use]
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.into_iter()
+    }
+
+    /// Returns an iterator over the keys in this map.
+    #[must_use]
+    #[inline]
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &K> + ExactSizeIterator + '_ {
+        self.iter().map(Field::key)
+    }
+
+    /// Returns an iterator over the values in this map.
+    #[must_use]
+    #[inline]
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> + ExactSizeIterator + '_ {
+        self.iter().map(|field| &field.value)
+    }
+
+    /// Returns an iterator over the fields whose keys are contained in
+    /// `range`.
+    #[inline]
+    pub fn range<SearchFor, R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        K: Sort<SearchFor>,
+        R: RangeBounds<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(key) => self.position(key, false),
+            Bound::Excluded(key) => self.position(key, true),
+            Bound::Unbounded => (0, 0),
+        };
+        let end = match range.end_bound() {
+            Bound::Included(key) => self.position(key, true),
+            Bound::Excluded(key) => self.position(key, false),
+            Bound::Unbounded => self.end_position(),
+        };
+        Iter::new(&self.chunks, start, end.max(start))
+    }
+
+    /// Returns the fields of this map as a [`Map`].
+    #[must_use]
+    pub fn into_map(self) -> Map<K, V> {
+        let mut fields = Vec::with_capacity(self.len);
+        for chunk in self.chunks {
+            fields.extend(chunk);
+        }
+        Map::from_sorted_fields(fields)
+    }
+
+    /// Removes all fields from this map.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.chunks.clear();
+        self.len = 0;
+    }
+
+    /// Returns the chunk that contains or should contain `key`, and the result
+    /// of searching that chunk for `key`.
+    fn locate<SearchFor>(&self, key: &SearchFor) -> (usize, Result<usize, usize>)
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        let chunk = self
+            .chunks
+            .partition_point(|chunk| Self::last_key(chunk).compare(key) == Ordering::Less)
+            .min(self.chunks.len().saturating_sub(1));
+        match self.chunks.get(chunk) {
+            Some(fields) => (chunk, fields.find_key_index(key)),
+            None => (0, Err(0)),
+        }
+    }
+
+    /// Returns the location of the first field whose key is greater than `key`
+    /// if `after_equal` is true, or greater than or equal to `key` otherwise.
+    fn position<SearchFor>(&self, key: &SearchFor, after_equal: bool) -> (usize, usize)
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        let chunk = self
+            .chunks
+            .partition_point(|chunk| match Self::last_key(chunk).compare(key) {
+                Ordering::Less => true,
+                Ordering::Equal => after_equal,
+                Ordering::Greater => false,
+            });
+        match self.chunks.get(chunk) {
+            Some(fields) => match fields.find_key_index(key) {
+                Ok(index) => (chunk, index + usize::from(after_equal)),
+                Err(index) => (chunk, index),
+            },
+            None => self.end_position(),
+        }
+    }
+
+    fn end_position(&self) -> (usize, usize) {
+        self.chunks
+            .last()
+            .map_or((0, 0), |last| (self.chunks.len() - 1, last.len()))
+    }
+
+    fn last_key(chunk: &Map<K, V>) -> &K {
+        chunk.fields()[chunk.len() - 1].key()
+    }
+
+    fn chunk_of_index(&self, mut index: usize) -> Option<(usize, usize)> {
+        for (chunk, fields) in self.chunks.iter().enumerate() {
+            if index < fields.len() {
+                return Some((chunk, index));
+            }
+            index -= fields.len();
+        }
+        None
+    }
+
+    /// Inserts `field` at `index` in `chunk`, returning the location of the
+    /// inserted field after any split.
+    fn insert_at(&mut self, chunk: usize, index: usize, field: Field<K, V>) -> (usize, usize) {
+        if self.chunks.is_empty() {
+            self.chunks.push(Map::new());
+        }
+        self.chunks[chunk].splice_fields(index..index, Some(field));
+        self.len += 1;
+
+        let chunk_len = self.chunks[chunk].len();
+        if chunk_len > MAX_CHUNK_LEN {
+            let middle = chunk_len / 2;
+            let upper = self.chunks[chunk].split_off(middle);
+            self.chunks.insert(chunk + 1, upper);
+            if index >= middle {
+                return (chunk + 1, index - middle);
+            }
+        }
+        (chunk, index)
+    }
+
+    /// Removes the field at `index` in `chunk`, removing or merging the chunk if
+    /// it becomes empty or small.
+    fn remove_at(&mut self, chunk: usize, index: usize) -> Field<K, V> {
+        let removed = self.chunks[chunk].remove_by_index(index);
+        self.len -= 1;
+
+        let chunk_len = self.chunks[chunk].len();
+        if chunk_len == 0 {
+            self.chunks.remove(chunk);
+        } else if chunk_len < MAX_CHUNK_LEN / 4 {
+            let (lower, upper) = if chunk + 1 < self.chunks.len() {
+                (chunk, chunk + 1)
+            } else if chunk > 0 {
+                (chunk - 1, chunk)
+            } else {
+                return removed;
+            };
+            if self.chunks[lower].len() + self.chunks[upper].len() <= MAX_CHUNK_LEN {
+                let upper = self.chunks.remove(upper);
+                let end = self.chunks[lower].len();
+                self.chunks[lower].splice_fields(end..end, upper);
+            }
+        }
+        removed
+    }
+}
+
+impl<K, V> PartialEq for ChunkedMap<K, V>
+where
+    K: Sort<K> + PartialEq,
+    V: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<K, V> Eq for ChunkedMap<K, V>
+where
+    K: Sort<K> + Eq,
+    V: Eq,
+{
+}
+
+impl<K, V> Debug for ChunkedMap<K, V>
+where
+    K: Sort<K> + Debug,
+    V: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_map();
+        for field in self {
+            s.entry(field.key(), &field.value);
+        }
+        s.finish()
+    }
+}
+
+impl<K, V> From<Map<K, V>> for ChunkedMap<K, V>
+where
+    K: Sort<K>,
+{
+    fn from(map: Map<K, V>) -> Self {
+        let len = map.len();
+        let mut chunks = Vec::with_capacity((len + MAX_CHUNK_LEN / 2 - 1) / (MAX_CHUNK_LEN / 2));
+        let mut fields = map.into_iter().peekable();
+        while fields.peek().is_some() {
+            chunks.push(Map::from_sorted_fields(
+                fields.by_ref().take(MAX_CHUNK_LEN / 2).collect(),
+            ));
+        }
+        Self { chunks, len }
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a ChunkedMap<K, V>
+where
+    K: Sort<K>,
+{
+    type IntoIter = Iter<'a, K, V>;
+    type Item = &'a Field<K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        Iter::new(&self.chunks, (0, 0), self.end_position())
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for ChunkedMap<K, V>
+where
+    K: Sort<K>,
+{
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::from(Map::from_iter(iter))
+    }
+}
+
+impl<K, V> Extend<(K, V)> for ChunkedMap<K, V>
+where
+    K: Sort<K>,
+{
+    #[inline]
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+/// An entry in a [`ChunkedMap`].
+pub enum Entry<'a, K, V>
+where
+    K: Sort<K>,
+{
+    /// An entry whose key is contained in the map.
+    Occupied(OccupiedEntry<'a, K, V>),
+    /// An entry whose key is not contained in the map.
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+impl<'a, K, V> Entry<'a, K, V>
+where
+    K: Sort<K>,
+{
+    /// Calls `update` with the value of this entry if it is occupied.
+    #[must_use]
+    #[inline]
+    pub fn and_modify(self, update: impl FnOnce(&mut V)) -> Self {
+        match self {
+            Entry::Occupied(mut entry) => {
+                update(&mut entry);
+                Entry::Occupied(entry)
+            }
+            vacant @ Entry::Vacant(_) => vacant,
+        }
+    }
+
+    /// Returns a mutable reference to the value of this entry, inserting the
+    /// result of `contents()` if this entry is vacant.
+    #[inline]
+    pub fn or_insert_with(self, contents: impl FnOnce() -> V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(contents()),
+        }
+    }
+
+    /// Returns a mutable reference to the value of this entry, inserting
+    /// `value` if this entry is vacant.
+    #[inline]
+    pub fn or_insert(self, value: V) -> &'a mut V {
+        self.or_insert_with(|| value)
+    }
+
+    /// Returns a mutable reference to the value of this entry, inserting
+    /// `V::default()` if this entry is vacant.
+    #[inline]
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+}
+
+/// An entry in a [`ChunkedMap`] whose key is contained in the map.
+pub struct OccupiedEntry<'a, K, V>
+where
+    K: Sort<K>,
+{
+    map: &'a mut ChunkedMap<K, V>,
+    chunk: usize,
+    index: usize,
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V>
+where
+    K: Sort<K>,
+{
+    fn field(&self) -> &Field<K, V> {
+        &self.map.chunks[self.chunk].fields()[self.index]
+    }
+
+    /// Converts this entry into a mutable reference to the value.
+    #[must_use]
+    #[inline]
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.map.chunks[self.chunk].fields_mut()[self.index].value
+    }
+
+    /// Returns the key of this entry.
+    #[must_use]
+    #[inline]
+    pub fn key(&self) -> &K {
+        self.field().key()
+    }
+
+    /// Replaces the value of this entry with `value`, and returns the existing
+    /// value.
+    #[inline]
+    pub fn replace(self, value: V) -> V {
+        mem::replace(self.into_mut(), value)
+    }
+
+    /// Removes this entry from the map, and returns the field.
+    #[must_use]
+    #[inline]
+    pub fn remove(self) -> Field<K, V> {
+        self.map.remove_at(self.chunk, self.index)
+    }
+}
+
+impl<K, V> Deref for OccupiedEntry<'_, K, V>
+where
+    K: Sort<K>,
+{
+    type Target = V;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.field().value
+    }
+}
+
+impl<K, V> DerefMut for OccupiedEntry<'_, K, V>
+where
+    K: Sort<K>,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.map.chunks[self.chunk].fields_mut()[self.index].value
+    }
+}
+
+/// An entry in a [`ChunkedMap`] whose key is not contained in the map.
+pub struct VacantEntry<'a, K, V>
+where
+    K: Sort<K>,
+{
+    map: &'a mut ChunkedMap<K, V>,
+    key: K,
+    chunk: usize,
+    index: usize,
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V>
+where
+    K: Sort<K>,
+{
+    /// Returns the key of this entry.
+    #[must_use]
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Inserts `value` into the map, returning a mutable reference to the
+    /// inserted value.
+    #[inline]
+    pub fn insert(self, value: V) -> &'a mut V {
+        let (chunk, index) =
+            self.map
+                .insert_at(self.chunk, self.index, Field::new(self.key, value));
+        &mut self.map.chunks[chunk].fields_mut()[index].value
+    }
+}
+
+/// An iterator over the fields in a [`ChunkedMap`].
+///
+/// This iterator is guaranteed to return results in the sort order of the `K`
+/// type.
+pub struct Iter<'a, K, V>
+where
+    K: Sort<K>,
+{
+    front: slice::Iter<'a, Field<K, V>>,
+    chunks: slice::Iter<'a, Map<K, V>>,
+    back: slice::Iter<'a, Field<K, V>>,
+    remaining: usize,
+}
+
+impl<'a, K, V> Iter<'a, K, V>
+where
+    K: Sort<K>,
+{
+    fn new(chunks: &'a [Map<K, V>], start: (usize, usize), end: (usize, usize)) -> Self {
+        let (start_chunk, start_index) = start;
+        let (end_chunk, end_index) = end;
+        let Some(first) = chunks.get(start_chunk) else {
+            return Self {
+                front: [].iter(),
+                chunks: [].iter(),
+                back: [].iter(),
+                remaining: 0,
+            };
+        };
+        if start_chunk == end_chunk {
+            let front = first.fields()[start_index..end_index].iter();
+            return Self {
+                remaining: front.len(),
+                front,
+                chunks: [].iter(),
+                back: [].iter(),
+            };
+        }
+
+        let front = first.fields()[start_index..].iter();
+        let middle = &chunks[start_chunk + 1..end_chunk];
+        let back = chunks[end_chunk].fields()[..end_index].iter();
+        Self {
+            remaining: front.len() + middle.iter().map(Map::len).sum::<usize>() + back.len(),
+            front,
+            chunks: middle.iter(),
+            back,
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V>
+where
+    K: Sort<K>,
+{
+    type Item = &'a Field<K, V>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(field) = self.front.next() {
+                self.remaining -= 1;
+                return Some(field);
+            }
+            match self.chunks.next() {
+                Some(chunk) => self.front = chunk.fields().iter(),
+                None => break,
+            }
+        }
+        let field = self.back.next()?;
+        self.remaining -= 1;
+        Some(field)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> where K: Sort<K> {}
+
+impl<K, V> DoubleEndedIterator for Iter<'_, K, V>
+where
+    K: Sort<K>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(field) = self.back.next_back() {
+                self.remaining -= 1;
+                return Some(field);
+            }
+            match self.chunks.next_back() {
+                Some(chunk) => self.back = chunk.fields().iter(),
+                None => break,
+            }
+        }
+        let field = self.front.next_back()?;
+        self.remaining -= 1;
+        Some(field)
+    }
+}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> where K: Sort<K> {}
+
+#[cfg(test)]
+fn shuffled(len: u32) -> impl Iterator<Item = u32> {
+    // A full-period linear congruential generator over `0..len.next_power_of_two()`.
+    let modulus = len.next_power_of_two();
+    let mut state = 0_u32;
+    core::iter::repeat_with(move || {
+        state = state.wrapping_mul(5).wrapping_add(3) & (modulus - 1);
+        state
+    })
+    .take(modulus as usize)
+    .filter(move |value| *value < len)
+}
+
+#[test]
+fn insert_and_remove() {
+    let mut chunked = ChunkedMap::new();
+    let mut map = Map::new();
+    for key in shuffled(3_000) {
+        assert_eq!(chunked.insert(key, key), map.insert(key, key));
+    }
+    assert_eq!(chunked.len(), 3_000);
+    assert!(chunked.chunks.len() > 1);
+    assert!(chunked.iter().eq(map.iter()));
+    assert!(chunked.iter().rev().eq(map.iter().rev()));
+    assert_eq!(
+        chunked.insert(7, 70).map(Field::into_parts),
+        map.insert(7, 70).map(Field::into_parts)
+    );
+
+    for index in [0, 1, 255, 256, 257, 1_500, 2_999, 3_000] {
+        assert_eq!(chunked.field(index), map.field(index));
+    }
+    for key in (0..3_000).step_by(7) {
+        assert_eq!(chunked.get(&key), map.get(&key));
+    }
+
+    for key in shuffled(3_000).filter(|key| key % 5 != 0) {
+        assert_eq!(chunked.remove(&key), map.remove(&key));
+        assert_eq!(chunked.remove(&key), None);
+    }
+    assert!(chunked.chunks.iter().all(|chunk| !chunk.is_empty()));
+    assert_eq!(chunked.len(), map.len());
+    assert!(chunked.iter().eq(map.iter()));
+    assert_eq!(chunked.remove_by_index(1), map.remove_by_index(1));
+    assert_eq!(chunked.clone().into_map(), map);
+
+    chunked.clear();
+    assert!(chunked.is_empty());
+    assert_eq!(chunked.get(&0), None);
+    assert_eq!(chunked.remove(&0), None);
+    assert!(chunked.iter().next().is_none());
+}
+
+#[test]
+fn ranges() {
+    let chunked = (0_u32..2_000)
+        .map(|key| (key * 2, key))
+        .collect::<ChunkedMap<_, _>>();
+    let map = chunked.clone().into_map();
+    for (start, end) in [(0, 0), (0, 10), (255, 1_030), (511, 513), (3_990, 4_100)] {
+        assert!(chunked.range(start..end).eq(map.range(start..end)));
+        assert!(chunked.range(start..=end).eq(map.range(start..=end)));
+        assert!(chunked.range(start..).eq(map.range(start..)));
+        assert!(chunked.range(..end).rev().eq(map.range(..end).rev()));
+        assert!(chunked
+            .range((Bound::Excluded(start), Bound::Included(end)))
+            .eq(map.range((Bound::Excluded(start), Bound::Included(end)))));
+        assert_eq!(chunked.range(start..end).len(), map.range(start..end).len());
+    }
+    let (start, end) = (10, 4);
+    assert_eq!(chunked.range(start..end).len(), 0);
+    assert!(chunked.keys().copied().eq((0..2_000).map(|key| key * 2)));
+    assert!(chunked.values().rev().copied().eq((0..2_000).rev()));
+}
+
+#[test]
+fn entries() {
+    let mut chunked = ChunkedMap::new();
+    for key in shuffled(1_000) {
+        chunked
+            .entry(key % 600)
+            .and_modify(|count| *count += 1)
+            .or_insert(1);
+    }
+    assert_eq!(chunked.len(), 600);
+    assert!(chunked
+        .iter()
+        .all(|field| field.value == if *field.key() < 400 { 2 } else { 1 }));
+
+    let Entry::Occupied(mut entry) = chunked.entry(5) else {
+        unreachable!("5 was inserted")
+    };
+    assert_eq!(entry.key(), &5);
+    *entry += 1;
+    assert_eq!(entry.replace(10), 3);
+    let Entry::Occupied(entry) = chunked.entry(5) else {
+        unreachable!("5 was inserted")
+    };
+    assert_eq!(entry.remove().into_parts(), (5, 10));
+
+    let Entry::Vacant(entry) = chunked.entry(5) else {
+        unreachable!("5 was removed")
+    };
+    assert_eq!(entry.key(), &5);
+    *entry.insert(1) += 1;
+    assert_eq!(chunked.get(&5), Some(&2));
+    *chunked.entry(1_000).or_default() += 4;
+    assert_eq!(
+        chunked.field(600).map(|field| (*field.key(), field.value)),
+        Some((1_000, 4))
+    );
+}
@@ -12,13 +12,49 @@ extern crate std;
 
 extern crate alloc;
 
+/// Types supporting the [`AdaptiveMap<K, V>`] collection type.
+pub mod adaptivemap;
+/// Types supporting the [`AugmentedMap<K, V, M>`] collection type.
+pub mod augmentedmap;
+/// Types supporting the [`Bag<T>`] collection type.
+pub mod bag;
+/// Types supporting the [`BiMap<L, R>`] collection type.
+pub mod bimap;
+/// Types supporting the [`ChunkedMap<K, V>`] collection type.
+pub mod chunkedmap;
+/// Types supporting the [`FrozenMap<K, V>`] collection type.
+pub mod frozenmap;
 /// Types supporting the [`Map<Key, Value>`] collection type.
 pub mod map;
+/// Types supporting the [`MultiMap<K, V>`] collection type.
+pub mod multimap;
+/// Types supporting the [`RangeMap<K, V>`] collection type.
+pub mod rangemap;
+/// Types supporting the [`RangeSet<T>`] collection type.
+pub mod rangeset;
 /// Types supporting the [`Set<T>`] collection type.
 pub mod set;
+/// Types supporting the [`SortedVec<T>`] collection type.
+pub mod sortedvec;
+/// Copy-on-write collections that share their storage between clones.
+#[cfg(target_has_atomic = "ptr")]
+pub mod sync;
+/// Types supporting the [`TopK<K, V>`] collection type.
+pub mod topk;
 
+pub use adaptivemap::AdaptiveMap;
+pub use augmentedmap::AugmentedMap;
+pub use bag::Bag;
+pub use bimap::BiMap;
+pub use chunkedmap::ChunkedMap;
+pub use frozenmap::FrozenMap;
 pub use map::Map;
+pub use multimap::MultiMap;
+pub use rangemap::RangeMap;
+pub use rangeset::RangeSet;
 pub use set::Set;
+pub use sortedvec::SortedVec;
+pub use topk::TopK;
 
 #[cfg(feature = "serde")]
 mod serde;
@@ -1,13 +1,16 @@
 use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
 use alloc::vec::{self, Vec};
 use core::alloc::Layout;
 use core::borrow::Borrow;
 use core::cmp::Ordering;
 use core::fmt::{self, Debug};
 use core::iter::{FusedIterator, Peekable};
-use core::ops::{Deref, DerefMut};
+use core::marker::PhantomData;
+use core::ops::{Bound, Deref, DerefMut, Range, RangeBounds};
 use core::{mem, slice};
 
+use crate::frozenmap::FrozenMap;
 use crate::Sort;
 
 /// An ordered Key/Value map.
@@ -45,7 +48,7 @@ where
 /// This uses the key and value types's layout to calculate based on multiple
 /// cache line widths. Magic numbers are a code smell, but I'm not sure how else
 /// to tune this heuristic based on the information available at compile time.
-const fn scan_limit<Key, Value>() -> usize {
+pub(crate) const fn scan_limit<Key, Value>() -> usize {
     let field_layout = Layout::new::<Field<Key, Value>>();
     let align = field_layout.align();
     let aligned = ((field_layout.size() + (align - 1)) / align) * align;
@@ -74,6 +77,34 @@ fn scan_limit_tests() {
     assert_eq!(scan_limit::<(u128, u128), (u128, u128)>(), 4);
 }
 
+/// Returns true if searching for each of `small`'s keys individually is
+/// expected to be cheaper than walking both collections in order.
+///
+/// A walk visits `small + large` fields, while searching costs at most
+/// `log2(large)` comparisons for each of `small`'s keys.
+fn prefers_search(small: usize, large: usize) -> bool {
+    let search_depth = (usize::BITS - large.leading_zeros()) as usize;
+    small.saturating_mul(search_depth) < small + large
+}
+
+/// Returns true if none of the keys in `small` are contained in `large`, by
+/// galloping through `large` for each key in `small`.
+fn galloping_disjoint<K, A, B>(small: &[Field<K, A>], large: &[Field<K, B>]) -> bool
+where
+    K: Sort,
+{
+    let mut remaining = large;
+    for field in small {
+        remaining = skip_lesser(remaining, &field.key);
+        match remaining.first() {
+            Some(found) if found.key.compare(&field.key) == Ordering::Equal => return false,
+            Some(_) => {}
+            None => break,
+        }
+    }
+    true
+}
+
 impl<Key, Value> Map<Key, Value>
 where
     Key: Sort<Key>,
@@ -105,6 +136,35 @@ where
         self.fields.capacity()
     }
 
+    /// Returns a map containing `fields` after sorting them by key.
+    ///
+    /// If multiple fields have the same key, only the last field is kept. This
+    /// matches the behavior of calling [`Map::insert`] for each field.
+    pub(crate) fn from_unsorted_fields(mut fields: Vec<Field<Key, Value>>) -> Self {
+        // A stable sort keeps the fields with equal keys in their original
+        // order, allowing the dedup to keep the last of them.
+        fields.sort_by(|a, b| a.key.compare(&b.key));
+        fields.dedup_by(|later, earlier| {
+            if later.key.compare(&earlier.key) == Ordering::Equal {
+                mem::swap(later, earlier);
+                true
+            } else {
+                false
+            }
+        });
+        Self { fields }
+    }
+
+    /// Returns a map containing `fields`, which must already be sorted by key
+    /// and contain no duplicate keys.
+    #[inline]
+    pub(crate) fn from_sorted_fields(fields: Vec<Field<Key, Value>>) -> Self {
+        debug_assert!(fields
+            .windows(2)
+            .all(|pair| pair[0].key.compare(&pair[1].key) == Ordering::Less));
+        Self { fields }
+    }
+
     /// Inserts `key` and `value`. If an entry already existed for `key`, the
     /// value being overwritten is returned.
     #[inline]
@@ -139,6 +199,27 @@ where
         }
     }
 
+    /// Returns a mutable reference to the value for `key`, inserting the
+    /// result of `value()` if the map does not already contain `key`.
+    ///
+    /// Unlike [`Map::entry`], this function does not require that `Key`
+    /// implement [`ToOwned`].
+    #[inline]
+    pub(crate) fn get_or_insert_with(
+        &mut self,
+        key: Key,
+        value: impl FnOnce() -> Value,
+    ) -> &mut Value {
+        let index = match self.find_key_index(&key) {
+            Ok(index) => index,
+            Err(insert_at) => {
+                self.fields.insert(insert_at, Field::new(key, value()));
+                insert_at
+            }
+        };
+        &mut self.fields[index].value
+    }
+
     /// Returns true if this object contains `key`.
     #[inline]
     pub fn contains<SearchFor>(&self, key: &SearchFor) -> bool
@@ -190,6 +271,83 @@ where
         self.find_key_mut(key).ok()
     }
 
+    /// Returns the field with the greatest key that is less than or equal to
+    /// `key`, if found.
+    ///
+    /// ```rust
+    /// use kempt::Map;
+    ///
+    /// let map = Map::from([(1, 'a'), (5, 'b')]);
+    /// assert_eq!(map.floor(&0), None);
+    /// assert_eq!(map.floor(&1).map(|field| field.value), Some('a'));
+    /// assert_eq!(map.floor(&4).map(|field| field.value), Some('a'));
+    /// assert_eq!(map.floor(&7).map(|field| field.value), Some('b'));
+    /// ```
+    #[inline]
+    pub fn floor<SearchFor>(&self, key: &SearchFor) -> Option<&Field<Key, Value>>
+    where
+        Key: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.floor_index(key).map(|index| &self.fields[index])
+    }
+
+    /// Returns the index of the field with the greatest key that is less than
+    /// or equal to `key`, if found.
+    #[inline]
+    pub(crate) fn floor_index<SearchFor>(&self, key: &SearchFor) -> Option<usize>
+    where
+        Key: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        match self.find_key_index(key) {
+            Ok(index) => Some(index),
+            Err(insert_at) => insert_at.checked_sub(1),
+        }
+    }
+
+    /// Returns the fields of this map.
+    #[inline]
+    pub(crate) fn fields(&self) -> &[Field<Key, Value>] {
+        &self.fields
+    }
+
+    /// Splits this map at `index`, returning a map containing the fields at and
+    /// after `index`.
+    #[inline]
+    pub(crate) fn split_off(&mut self, index: usize) -> Self {
+        Self {
+            fields: self.fields.split_off(index),
+        }
+    }
+
+    /// Returns the fields of this map. Keys must not be modified in a way that
+    /// changes their sort order.
+    #[inline]
+    pub(crate) fn fields_mut(&mut self) -> &mut [Field<Key, Value>] {
+        &mut self.fields
+    }
+
+    /// Replaces the fields in `range` with `replacement`, which must keep the
+    /// fields of this map in sort order with no duplicates.
+    #[inline]
+    pub(crate) fn splice_fields(
+        &mut self,
+        range: Range<usize>,
+        replacement: impl IntoIterator<Item = Field<Key, Value>>,
+    ) {
+        let retained = self.fields.len() - range.len();
+        let start = range.start;
+        self.fields.splice(range, replacement);
+        // Only the replacement and its neighbors can be out of order, so only
+        // check the boundaries of the spliced region.
+        let inserted = self.fields.len() - retained;
+        let checked = start.saturating_sub(1)..(start + inserted + 1).min(self.fields.len());
+        debug_assert!(self.fields[checked]
+            .windows(2)
+            .all(|pair| pair[0].key.compare(&pair[1].key) == Ordering::Less));
+    }
+
     /// Returns the [`Field`] at the specified `index`, or None if the index is
     /// outside of the bounds of this collection.
     #[inline]
@@ -280,7 +438,7 @@ where
             .map(|index| &mut self.fields[index])
     }
 
-    fn find_key_index<SearchFor>(&self, search_for: &SearchFor) -> Result<usize, usize>
+    pub(crate) fn find_key_index<SearchFor>(&self, search_for: &SearchFor) -> Result<usize, usize>
     where
         Key: Sort<SearchFor>,
         SearchFor: ?Sized,
@@ -341,6 +499,77 @@ where
         Keys(self.fields.iter())
     }
 
+    /// Returns an iterator returning all of the keys contained in this object.
+    #[must_use]
+    #[inline]
+    pub fn into_keys(self) -> IntoKeys<Key, Value> {
+        IntoKeys(self.fields.into_iter())
+    }
+
+    /// Returns an iterator over the fields whose keys are contained in
+    /// `range`.
+    ///
+    /// If the start of `range` is greater than its end, the returned iterator
+    /// will be empty.
+    ///
+    /// ```rust
+    /// use kempt::Map;
+    ///
+    /// let map: Map<u8, char> = [(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')]
+    ///     .into_iter()
+    ///     .collect();
+    /// let in_range = map
+    ///     .range(&2..&4)
+    ///     .map(|field| field.value)
+    ///     .collect::<String>();
+    /// assert_eq!(in_range, "bc");
+    /// ```
+    #[inline]
+    pub fn range<SearchFor, R>(&self, range: R) -> Iter<'_, Key, Value>
+    where
+        Key: Sort<SearchFor>,
+        R: RangeBounds<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        Iter(self.fields[self.range_indices(range)].iter())
+    }
+
+    /// Returns an iterator over the keys contained in `range`.
+    #[inline]
+    pub(crate) fn range_keys<SearchFor, R>(&self, range: R) -> Keys<'_, Key, Value>
+    where
+        Key: Sort<SearchFor>,
+        R: RangeBounds<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        Keys(self.fields[self.range_indices(range)].iter())
+    }
+
+    pub(crate) fn range_indices<SearchFor, R>(&self, range: R) -> Range<usize>
+    where
+        Key: Sort<SearchFor>,
+        R: RangeBounds<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(key) => self.find_key_index(key).unwrap_or_else(|index| index),
+            Bound::Excluded(key) => match self.find_key_index(key) {
+                Ok(index) => index + 1,
+                Err(index) => index,
+            },
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(key) => match self.find_key_index(key) {
+                Ok(index) => index + 1,
+                Err(index) => index,
+            },
+            Bound::Excluded(key) => self.find_key_index(key).unwrap_or_else(|index| index),
+            Bound::Unbounded => self.fields.len(),
+        };
+        start..end.max(start)
+    }
+
     /// Returns an iterator over the values in this object.
     #[must_use]
     #[inline]
@@ -363,6 +592,16 @@ where
         IntoValues(self.fields.into_iter())
     }
 
+    /// Converts this map into a read-only [`FrozenMap`], which stores its
+    /// fields in a layout optimized for lookups.
+    ///
+    /// The map can be converted back using [`FrozenMap::thaw`].
+    #[must_use]
+    #[inline]
+    pub fn freeze(self) -> FrozenMap<Key, Value> {
+        FrozenMap::from(self)
+    }
+
     /// Merges the fields from `self` and `other` into a new object, returning
     /// the updated object.
     ///
@@ -434,6 +673,9 @@ where
     ) where
         Key: Clone,
     {
+        // When `self` is much larger than `other`, gallop past the runs of keys
+        // that `other` doesn't contain.
+        let gallop = prefers_search(other.len(), self.len());
         let mut self_index = 0;
         let mut other_index = 0;
 
@@ -443,7 +685,11 @@ where
             match Key::compare(&self_field.key, &other_field.key) {
                 Ordering::Less => {
                     // Self has a key that other didn't.
-                    self_index += 1;
+                    self_index += if gallop {
+                        gallop_skip_count(&self.fields[self_index..], &other_field.key)
+                    } else {
+                        1
+                    };
                 }
                 Ordering::Equal => {
                     // Both have the value, we might need to merge.
@@ -477,6 +723,98 @@ where
         }
     }
 
+    /// Retains only the fields whose keys are contained in `other` when
+    /// `found` is true, or the fields whose keys are not contained in `other`
+    /// when `found` is false.
+    ///
+    /// Because both maps are sorted, this is performed in a single pass over
+    /// both collections.
+    pub(crate) fn retain_keys_found_in<OtherValue>(
+        &mut self,
+        other: &Map<Key, OtherValue>,
+        found: bool,
+    ) {
+        let mut other_fields = other.fields.iter().peekable();
+        self.fields.retain(|field| {
+            while let Some(other_field) = other_fields.peek() {
+                match field.key.compare(&other_field.key) {
+                    Ordering::Less => break,
+                    Ordering::Equal => return found,
+                    Ordering::Greater => {
+                        other_fields.next();
+                    }
+                }
+            }
+            !found
+        });
+    }
+
+    /// Merges the fields from `other` into `self`, taking ownership of
+    /// `other`'s fields.
+    ///
+    /// * If a field is contained in `other` but not contained in `self`, the
+    ///   field is moved into `self`.
+    /// * If a field is contained in both `other` and `self`, `merge()` is
+    ///   called with mutable access to the value from `self` and the owned
+    ///   value from `other`. The `merge()` function is responsible for
+    ///   updating the value if needed to complete the merge.
+    /// * If a field is contained in `self` but not in `other`, it is kept.
+    ///
+    /// Unlike [`merge_with()`](Self::merge_with), this function does not
+    /// require `Key` to implement `Clone`, and no values are cloned. Both
+    /// collections are merged in a single pass into a new allocation, so no
+    /// fields are shifted when inserting `other`'s fields.
+    ///
+    /// If `merge()` panics, `self` keeps all of its fields, along with the
+    /// fields from `other` that were merged before the panic. The remaining
+    /// fields from `other` are dropped.
+    ///
+    /// ```rust
+    /// use kempt::Map;
+    ///
+    /// let mut a: Map<&'static str, Vec<usize>> = [("a", vec![1]), ("b", vec![2])].into_iter().collect();
+    /// let b: Map<&'static str, Vec<usize>> = [("a", vec![3]), ("c", vec![4])].into_iter().collect();
+    /// a.merge_from(b, |_key, a, mut b| a.append(&mut b));
+    /// assert_eq!(a.get(&"a"), Some(&vec![1, 3]));
+    /// assert_eq!(a.get(&"b"), Some(&vec![2]));
+    /// assert_eq!(a.get(&"c"), Some(&vec![4]));
+    /// ```
+    pub fn merge_from(&mut self, other: Self, mut merge: impl FnMut(&Key, &mut Value, Value)) {
+        if other.is_empty() {
+            return;
+        } else if self.is_empty() {
+            *self = other;
+            return;
+        }
+
+        let existing = mem::take(&mut self.fields);
+        let mut incoming = other.fields.into_iter().peekable();
+        let mut merge_into = MergeFromGuard {
+            merged: Vec::with_capacity(existing.len() + incoming.len()),
+            existing: existing.into_iter().peekable(),
+            fields: &mut self.fields,
+        };
+        while let (Some(field), Some(other_field)) = (merge_into.existing.peek(), incoming.peek()) {
+            match field.key.compare(&other_field.key) {
+                Ordering::Less => merge_into.merged.extend(merge_into.existing.next()),
+                Ordering::Equal => {
+                    if let (Some(field), Some(other_field)) =
+                        (merge_into.existing.next(), incoming.next())
+                    {
+                        // The field is pushed before calling `merge()` so that
+                        // it is kept if `merge()` panics.
+                        merge_into.merged.push(field);
+                        if let Some(field) = merge_into.merged.last_mut() {
+                            merge(&field.key, &mut field.value, other_field.value);
+                        }
+                    }
+                }
+                Ordering::Greater => merge_into.merged.extend(incoming.next()),
+            }
+        }
+        merge_into.merged.extend(incoming);
+    }
+
     /// Returns an iterator that returns all of the elements in this collection.
     /// After the iterator is dropped, this object will be empty.
     #[inline]
@@ -527,10 +865,7 @@ where
     #[must_use]
     #[inline]
     pub fn union<'a>(&'a self, other: &'a Self) -> Union<'a, Key, Value> {
-        Union {
-            left: self.iter().peekable(),
-            right: other.iter().peekable(),
-        }
+        Union(MergeWalk::new(&self.fields, &other.fields))
     }
 
     /// Returns an iterator that yields entries that appear in both `self` and
@@ -545,9 +880,273 @@ where
     #[must_use]
     #[inline]
     pub fn intersection<'a>(&'a self, other: &'a Self) -> Intersection<'a, Key, Value> {
-        Intersection {
-            left: self.iter().peekable(),
-            right: other.iter().peekable(),
+        Intersection(MergeWalk::new(&self.fields, &other.fields))
+    }
+
+    /// Returns a map containing the keys found in both `self` and `other`,
+    /// with a clone of each map's value.
+    ///
+    /// Both maps are walked in a single pass, producing the joined map without
+    /// needing to re-sort its fields. When one map is much larger than the
+    /// other, a galloping search skips runs of keys that can't be matched.
+    ///
+    /// ```rust
+    /// use kempt::Map;
+    ///
+    /// let names = Map::from([(1, "alice"), (2, "bob")]);
+    /// let scores = Map::from([(2, 20), (3, 30)]);
+    /// assert_eq!(names.inner_join(&scores), Map::from([(2, ("bob", 20))]));
+    /// ```
+    #[must_use]
+    pub fn inner_join<OtherValue>(
+        &self,
+        other: &Map<Key, OtherValue>,
+    ) -> Map<Key, (Value, OtherValue)>
+    where
+        Key: Clone,
+        Value: Clone,
+        OtherValue: Clone,
+    {
+        let mut joined = Vec::with_capacity(self.len().min(other.len()));
+        let mut walk = MergeWalk::new(&self.fields, &other.fields);
+        while let Some((key, value, other)) = walk.next_both() {
+            joined.push(Field::new(key.clone(), (value.clone(), other.clone())));
+        }
+        Map::from_sorted_fields(joined)
+    }
+
+    /// Returns a map containing every key found in `self`, with a clone of its
+    /// value and a clone of `other`'s value for the same key, if present.
+    ///
+    /// Both maps are walked in a single pass, producing the joined map without
+    /// needing to re-sort its fields.
+    ///
+    /// ```rust
+    /// use kempt::Map;
+    ///
+    /// let names = Map::from([(1, "alice"), (2, "bob")]);
+    /// let scores = Map::from([(2, 20), (3, 30)]);
+    /// assert_eq!(
+    ///     names.left_join(&scores),
+    ///     Map::from([(1, ("alice", None)), (2, ("bob", Some(20)))])
+    /// );
+    /// ```
+    #[must_use]
+    pub fn left_join<OtherValue>(
+        &self,
+        other: &Map<Key, OtherValue>,
+    ) -> Map<Key, (Value, Option<OtherValue>)>
+    where
+        Key: Clone,
+        Value: Clone,
+        OtherValue: Clone,
+    {
+        self.join(other, self.len(), |joined| match joined {
+            EitherOrBoth::Left(value) => Some((value.clone(), None)),
+            EitherOrBoth::Both(value, other) => Some((value.clone(), Some(other.clone()))),
+            EitherOrBoth::Right(_) => None,
+        })
+    }
+
+    /// Returns a map containing every key found in either `self` or `other`,
+    /// with clones of the values found for each key.
+    ///
+    /// Both maps are walked in a single pass, producing the joined map without
+    /// needing to re-sort its fields.
+    ///
+    /// ```rust
+    /// use kempt::map::EitherOrBoth;
+    /// use kempt::Map;
+    ///
+    /// let names = Map::from([(1, "alice"), (2, "bob")]);
+    /// let scores = Map::from([(2, 20), (3, 30)]);
+    /// assert_eq!(
+    ///     names.outer_join(&scores),
+    ///     Map::from([
+    ///         (1, EitherOrBoth::Left("alice")),
+    ///         (2, EitherOrBoth::Both("bob", 20)),
+    ///         (3, EitherOrBoth::Right(30)),
+    ///     ])
+    /// );
+    /// ```
+    #[must_use]
+    pub fn outer_join<OtherValue>(
+        &self,
+        other: &Map<Key, OtherValue>,
+    ) -> Map<Key, EitherOrBoth<Value, OtherValue>>
+    where
+        Key: Clone,
+        Value: Clone,
+        OtherValue: Clone,
+    {
+        self.join(other, self.len() + other.len(), |joined| {
+            Some(joined.cloned())
+        })
+    }
+
+    /// Returns a map containing the keys found in both `self` and `other`,
+    /// with each map's value.
+    ///
+    /// This is the consuming form of [`inner_join()`](Self::inner_join). No
+    /// keys or values are cloned.
+    #[must_use]
+    pub fn into_inner_join<OtherValue>(
+        self,
+        other: Map<Key, OtherValue>,
+    ) -> Map<Key, (Value, OtherValue)> {
+        let gallop = prefers_search(self.len().min(other.len()), self.len().max(other.len()));
+        let mut joined = Vec::with_capacity(self.len().min(other.len()));
+        let mut left = self.fields.into_iter();
+        let mut right = other.fields.into_iter();
+        while let (Some(field), Some(other_field)) =
+            (left.as_slice().first(), right.as_slice().first())
+        {
+            // Unmatched fields are dropped by advancing past them, skipping
+            // runs of them at once when galloping.
+            match field.key.compare(&other_field.key) {
+                Ordering::Less => {
+                    let skip = if gallop {
+                        gallop_skip_count(left.as_slice(), &other_field.key)
+                    } else {
+                        1
+                    };
+                    left.nth(skip - 1);
+                }
+                Ordering::Equal => {
+                    if let (Some(field), Some(other_field)) = (left.next(), right.next()) {
+                        joined.push(Field::new(field.key, (field.value, other_field.value)));
+                    }
+                }
+                Ordering::Greater => {
+                    let skip = if gallop {
+                        gallop_skip_count(right.as_slice(), &field.key)
+                    } else {
+                        1
+                    };
+                    right.nth(skip - 1);
+                }
+            }
+        }
+        Map::from_sorted_fields(joined)
+    }
+
+    /// Returns a map containing every key found in `self`, with its value and
+    /// `other`'s value for the same key, if present.
+    ///
+    /// This is the consuming form of [`left_join()`](Self::left_join). No keys
+    /// or values are cloned.
+    #[must_use]
+    pub fn into_left_join<OtherValue>(
+        self,
+        other: Map<Key, OtherValue>,
+    ) -> Map<Key, (Value, Option<OtherValue>)> {
+        let capacity = self.len();
+        self.into_join(other, capacity, |joined| match joined {
+            EitherOrBoth::Left(value) => Some((value, None)),
+            EitherOrBoth::Both(value, other) => Some((value, Some(other))),
+            EitherOrBoth::Right(_) => None,
+        })
+    }
+
+    /// Returns a map containing every key found in either `self` or `other`,
+    /// with the values found for each key.
+    ///
+    /// This is the consuming form of [`outer_join()`](Self::outer_join). No
+    /// keys or values are cloned.
+    #[must_use]
+    pub fn into_outer_join<OtherValue>(
+        self,
+        other: Map<Key, OtherValue>,
+    ) -> Map<Key, EitherOrBoth<Value, OtherValue>> {
+        let capacity = self.len() + other.len();
+        self.into_join(other, capacity, Some)
+    }
+
+    /// Walks `self` and `other` in order, storing the result of `join` for
+    /// each key in the returned map when it returns `Some`. The returned map is
+    /// allocated with room for `capacity` fields.
+    fn join<'a, OtherValue, Joined>(
+        &'a self,
+        other: &'a Map<Key, OtherValue>,
+        capacity: usize,
+        mut join: impl FnMut(EitherOrBoth<&'a Value, &'a OtherValue>) -> Option<Joined>,
+    ) -> Map<Key, Joined>
+    where
+        Key: Clone,
+    {
+        let mut joined = Vec::with_capacity(capacity);
+        for fields in JoinWalk::new(self.fields.iter(), other.fields.iter()) {
+            let (key, values) = match fields {
+                EitherOrBoth::Left(field) => (&field.key, EitherOrBoth::Left(&field.value)),
+                EitherOrBoth::Right(other) => (&other.key, EitherOrBoth::Right(&other.value)),
+                EitherOrBoth::Both(field, other) => {
+                    (&field.key, EitherOrBoth::Both(&field.value, &other.value))
+                }
+            };
+            if let Some(value) = join(values) {
+                joined.push(Field::new(key.clone(), value));
+            }
+        }
+        Map::from_sorted_fields(joined)
+    }
+
+    /// Walks `self` and `other` in order, storing the result of `join` for
+    /// each key in the returned map when it returns `Some`. The returned map is
+    /// allocated with room for `capacity` fields.
+    fn into_join<OtherValue, Joined>(
+        self,
+        other: Map<Key, OtherValue>,
+        capacity: usize,
+        mut join: impl FnMut(EitherOrBoth<Value, OtherValue>) -> Option<Joined>,
+    ) -> Map<Key, Joined> {
+        let mut joined = Vec::with_capacity(capacity);
+        for fields in JoinWalk::new(self.fields.into_iter(), other.fields.into_iter()) {
+            let (key, values) = match fields {
+                EitherOrBoth::Left(field) => (field.key, EitherOrBoth::Left(field.value)),
+                EitherOrBoth::Right(other) => (other.key, EitherOrBoth::Right(other.value)),
+                EitherOrBoth::Both(field, other) => {
+                    (field.key, EitherOrBoth::Both(field.value, other.value))
+                }
+            };
+            if let Some(value) = join(values) {
+                joined.push(Field::new(key, value));
+            }
+        }
+        Map::from_sorted_fields(joined)
+    }
+
+    /// Returns an iterator that yields entries that appear in both `self` and
+    /// `other`, with exclusive access to the values in `self`.
+    ///
+    /// The iterator will return a result for each `Key` contained in both
+    /// `self` and `other`. If a particular key is only found in one collection,
+    /// it will not be included. Unlike [`intersection()`](Self::intersection),
+    /// `other` may contain a different value type.
+    ///
+    /// This iterator is guaranteed to return results in the sort order of the
+    /// `Key` type.
+    ///
+    /// ```rust
+    /// use kempt::Map;
+    ///
+    /// let mut balances = Map::from([("a", 10), ("b", 20), ("c", 30)]);
+    /// let deltas = Map::from([("b", -5_i64), ("d", 1)]);
+    /// for (_, balance, delta) in balances.intersection_mut(&deltas) {
+    ///     *balance += delta;
+    /// }
+    /// assert_eq!(balances, Map::from([("a", 10), ("b", 15), ("c", 30)]));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn intersection_mut<'a, OtherValue>(
+        &'a mut self,
+        other: &'a Map<Key, OtherValue>,
+    ) -> IntersectionMut<'a, Key, Value, OtherValue> {
+        let gallop = prefers_search(self.len().min(other.len()), self.len().max(other.len()));
+        IntersectionMut {
+            left: &mut self.fields,
+            right: &other.fields,
+            gallop,
         }
     }
 
@@ -563,38 +1162,307 @@ where
     #[must_use]
     #[inline]
     pub fn difference<'a>(&'a self, other: &'a Self) -> Difference<'a, Key, Value> {
-        Difference {
-            left: self.iter().peekable(),
-            right: other.iter().peekable(),
-        }
+        Difference(MergeWalk::new(&self.fields, &other.fields))
     }
-}
 
-impl<'a, SearchFor, Key, V> core::ops::Index<&'a SearchFor> for Map<Key, V>
-where
-    Key: Sort<Key>,
-    Key: Sort<SearchFor>,
-    SearchFor: ?Sized,
-{
-    type Output = V;
+    /// Returns true if every key in `self` is also contained in `other`.
+    ///
+    /// The values of the maps are not compared, which allows comparing the
+    /// keys of maps with different value types.
+    ///
+    /// ```rust
+    /// use kempt::Map;
+    ///
+    /// let a: Map<&'static str, usize> = [("a", 1)].into_iter().collect();
+    /// let b: Map<&'static str, bool> = [("a", true), ("b", false)].into_iter().collect();
+    /// assert!(a.keys_subset_of(&b));
+    /// assert!(!b.keys_subset_of(&a));
+    /// ```
+    #[must_use]
+    pub fn keys_subset_of<OtherValue>(&self, other: &Map<Key, OtherValue>) -> bool {
+        if self.len() > other.len() {
+            return false;
+        }
 
-    fn index(&self, index: &'a SearchFor) -> &Self::Output {
-        self.get(index).expect("key not found")
+        if prefers_search(self.len(), other.len()) {
+            let mut remaining = other.fields.as_slice();
+            for field in &self.fields {
+                remaining = skip_lesser(remaining, &field.key);
+                match remaining.split_first() {
+                    Some((found, rest)) if found.key.compare(&field.key) == Ordering::Equal => {
+                        remaining = rest;
+                    }
+                    _ => return false,
+                }
+            }
+            return true;
+        }
+
+        let mut other_fields = other.fields.iter();
+        'fields: for field in &self.fields {
+            for other_field in other_fields.by_ref() {
+                match field.key.compare(&other_field.key) {
+                    Ordering::Less => return false,
+                    Ordering::Equal => continue 'fields,
+                    Ordering::Greater => {}
+                }
+            }
+            return false;
+        }
+        true
     }
-}
 
-impl<'a, SearchFor, Key, V> core::ops::IndexMut<&'a SearchFor> for Map<Key, V>
-where
-    Key: Sort<Key>,
-    Key: Sort<SearchFor>,
-    SearchFor: ?Sized,
-{
-    fn index_mut(&mut self, index: &'a SearchFor) -> &mut Self::Output {
-        self.get_mut(index).expect("key not found")
+    /// Returns true if every key in `other` is also contained in `self`.
+    ///
+    /// The values of the maps are not compared, which allows comparing the
+    /// keys of maps with different value types.
+    #[must_use]
+    #[inline]
+    pub fn keys_superset_of<OtherValue>(&self, other: &Map<Key, OtherValue>) -> bool {
+        other.keys_subset_of(self)
     }
-}
 
-/// A key provided to the [`Map::entry`] function.
+    /// Returns true if `self` and `other` have no keys in common.
+    ///
+    /// The values of the maps are not compared, which allows comparing the
+    /// keys of maps with different value types.
+    #[must_use]
+    pub fn keys_disjoint_from<OtherValue>(&self, other: &Map<Key, OtherValue>) -> bool {
+        if prefers_search(self.len(), other.len()) {
+            return galloping_disjoint(&self.fields, &other.fields);
+        } else if prefers_search(other.len(), self.len()) {
+            return galloping_disjoint(&other.fields, &self.fields);
+        }
+
+        let mut self_index = 0;
+        let mut other_index = 0;
+        while self_index < self.len() && other_index < other.len() {
+            match self.fields[self_index]
+                .key
+                .compare(&other.fields[other_index].key)
+            {
+                Ordering::Less => self_index += 1,
+                Ordering::Equal => return false,
+                Ordering::Greater => other_index += 1,
+            }
+        }
+        true
+    }
+
+    /// Returns an iterator that yields entries that appear in either `self` or
+    /// `other`, but not in both.
+    ///
+    /// The iterator will return [`Unioned::Left`] for each `Key` only
+    /// contained in `self` and [`Unioned::Right`] for each `Key` only contained
+    /// in `other`. If a `Key` is in both collections, it will not be returned.
+    ///
+    /// This iterator is guaranteed to return results in the sort order of the
+    /// `Key` type.
+    ///
+    /// ```rust
+    /// use kempt::map::Unioned;
+    /// use kempt::Map;
+    ///
+    /// let a: Map<&'static str, usize> = [("a", 1), ("b", 2)].into_iter().collect();
+    /// let b: Map<&'static str, usize> = [("b", 2), ("c", 3)].into_iter().collect();
+    /// let mut changed = a.symmetric_difference(&b);
+    /// assert!(matches!(changed.next(), Some(Unioned::Left { key: &"a", .. })));
+    /// assert!(matches!(changed.next(), Some(Unioned::Right { key: &"c", .. })));
+    /// assert!(changed.next().is_none());
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn symmetric_difference<'a>(
+        &'a self,
+        other: &'a Self,
+    ) -> SymmetricDifference<'a, Key, Value> {
+        SymmetricDifference(MergeWalk::new(&self.fields, &other.fields))
+    }
+
+    /// Performs a three-way merge of `ours` and `theirs`, which were both
+    /// derived from `base`, returning the merged map.
+    ///
+    /// For each key, changes made by only one side are applied automatically.
+    /// If both sides made the same change, the change is applied once. If the
+    /// sides changed a key differently, `resolve()` is called with the key and
+    /// the [`Conflict`]. The [`Resolution`] returned determines the key's
+    /// value in the merged map.
+    ///
+    /// If any conflicts are [unresolved](Resolution::Unresolved), all
+    /// unresolved conflicts are returned in the sort order of the `Key` type
+    /// instead of the merged map.
+    ///
+    /// ```rust
+    /// use kempt::map::{Conflict, Resolution};
+    /// use kempt::Map;
+    ///
+    /// let base = Map::from([("a", 1), ("b", 2), ("c", 3)]);
+    /// let ours = Map::from([("a", 10), ("b", 2), ("c", 30)]);
+    /// let theirs = Map::from([("a", 1), ("b", 20), ("c", 300)]);
+    /// let merged = Map::merge3(&base, &ours, &theirs, |_key, conflict| match conflict {
+    ///     Conflict::EditEdit { ours, theirs, .. } => Resolution::Keep(*ours.max(theirs)),
+    ///     _ => Resolution::Unresolved,
+    /// })
+    /// .unwrap();
+    /// assert_eq!(merged, Map::from([("a", 10), ("b", 20), ("c", 300)]));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns each key and conflict for which `resolve()` returned
+    /// [`Resolution::Unresolved`].
+    pub fn merge3<'a>(
+        base: &'a Self,
+        ours: &'a Self,
+        theirs: &'a Self,
+        mut resolve: impl FnMut(&'a Key, Conflict<'a, Value>) -> Resolution<Value>,
+    ) -> Result<Self, Vec<(&'a Key, Conflict<'a, Value>)>>
+    where
+        Key: Clone,
+        Value: Clone + PartialEq,
+    {
+        let mut base = base.fields.as_slice();
+        let mut ours = ours.fields.as_slice();
+        let mut theirs = theirs.fields.as_slice();
+        let mut merged = Vec::with_capacity(ours.len().max(theirs.len()));
+        let mut unresolved = Vec::new();
+
+        while let Some(key) = [base.first(), ours.first(), theirs.first()]
+            .into_iter()
+            .flatten()
+            .map(Field::key)
+            .min_by(|a, b| a.compare(b))
+        {
+            let base_value = take_matching_value(&mut base, key);
+            let ours_value = take_matching_value(&mut ours, key);
+            let theirs_value = take_matching_value(&mut theirs, key);
+
+            let value = if ours_value == theirs_value || theirs_value == base_value {
+                ours_value.cloned()
+            } else if ours_value == base_value {
+                theirs_value.cloned()
+            } else {
+                let conflict = match (base_value, ours_value, theirs_value) {
+                    (Some(base), Some(ours), Some(theirs)) => {
+                        Conflict::EditEdit { base, ours, theirs }
+                    }
+                    (Some(base), Some(ours), None) => Conflict::EditDelete { base, ours },
+                    (Some(base), None, Some(theirs)) => Conflict::DeleteEdit { base, theirs },
+                    (None, Some(ours), Some(theirs)) => Conflict::AddAdd { ours, theirs },
+                    _ => unreachable!("at most one side differs from base"),
+                };
+                match resolve(key, conflict) {
+                    Resolution::Keep(value) => Some(value),
+                    Resolution::Remove => None,
+                    Resolution::Unresolved => {
+                        unresolved.push((key, conflict));
+                        None
+                    }
+                }
+            };
+
+            if let Some(value) = value {
+                merged.push(Field::new(key.clone(), value));
+            }
+        }
+
+        if unresolved.is_empty() {
+            Ok(Self::from_sorted_fields(merged))
+        } else {
+            Err(unresolved)
+        }
+    }
+
+    /// Returns an iterator that yields the [`Change`]s needed to turn `self`
+    /// into `other`.
+    ///
+    /// * Keys only contained in `other` are returned as [`Change::Added`].
+    /// * Keys only contained in `self` are returned as [`Change::Removed`].
+    /// * Keys contained in both whose values are not equal are returned as
+    ///   [`Change::Modified`].
+    ///
+    /// This iterator is guaranteed to return results in the sort order of the
+    /// `Key` type. The changes can be collected into a [`MapPatch`] to store
+    /// or apply them later.
+    ///
+    /// ```rust
+    /// use kempt::map::Change;
+    /// use kempt::Map;
+    ///
+    /// let old: Map<&'static str, usize> = [("a", 1), ("b", 2), ("c", 3)].into_iter().collect();
+    /// let new: Map<&'static str, usize> = [("b", 2), ("c", 4), ("d", 5)].into_iter().collect();
+    /// let mut changes = old.diff(&new);
+    /// assert_eq!(
+    ///     changes.next(),
+    ///     Some(Change::Removed {
+    ///         key: &"a",
+    ///         value: &1
+    ///     })
+    /// );
+    /// assert_eq!(
+    ///     changes.next(),
+    ///     Some(Change::Modified {
+    ///         key: &"c",
+    ///         old: &3,
+    ///         new: &4
+    ///     })
+    /// );
+    /// assert_eq!(
+    ///     changes.next(),
+    ///     Some(Change::Added {
+    ///         key: &"d",
+    ///         value: &5
+    ///     })
+    /// );
+    /// assert_eq!(changes.next(), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn diff<'a>(&'a self, other: &'a Self) -> Diff<'a, Key, Value> {
+        Diff(MergeWalk::new(&self.fields, &other.fields))
+    }
+}
+
+/// Returns the value of the first field in `fields` if its key matches `key`,
+/// advancing `fields` past it.
+fn take_matching_value<'a, K, V>(fields: &mut &'a [Field<K, V>], key: &K) -> Option<&'a V>
+where
+    K: Sort,
+{
+    match fields.split_first() {
+        Some((first, rest)) if first.key.compare(key) == Ordering::Equal => {
+            *fields = rest;
+            Some(&first.value)
+        }
+        _ => None,
+    }
+}
+
+impl<'a, SearchFor, Key, V> core::ops::Index<&'a SearchFor> for Map<Key, V>
+where
+    Key: Sort<Key>,
+    Key: Sort<SearchFor>,
+    SearchFor: ?Sized,
+{
+    type Output = V;
+
+    fn index(&self, index: &'a SearchFor) -> &Self::Output {
+        self.get(index).expect("key not found")
+    }
+}
+
+impl<'a, SearchFor, Key, V> core::ops::IndexMut<&'a SearchFor> for Map<Key, V>
+where
+    Key: Sort<Key>,
+    Key: Sort<SearchFor>,
+    SearchFor: ?Sized,
+{
+    fn index_mut(&mut self, index: &'a SearchFor) -> &mut Self::Output {
+        self.get_mut(index).expect("key not found")
+    }
+}
+
+/// A key provided to the [`Map::entry`] function.
 ///
 /// This is a [`Cow`](alloc::borrow::Cow)-like type that is slightly more
 /// flexible with `From` implementations. The `Owned` and `Borrowed` types are
@@ -710,13 +1578,86 @@ where
     #[inline]
     fn from_iter<T: IntoIterator<Item = (Key, Value)>>(iter: T) -> Self {
         let iter = iter.into_iter();
-        let mut obj = Self::with_capacity(iter.size_hint().0);
+        let mut fields = Vec::with_capacity(iter.size_hint().0);
         // Insert out of order, then sort before returning.
         for (key, value) in iter {
-            obj.fields.push(Field::new(key, value));
+            fields.push(Field::new(key, value));
         }
-        obj.fields.sort_unstable_by(|a, b| a.key().compare(b.key()));
-        obj
+        Self::from_unsorted_fields(fields)
+    }
+}
+
+impl<Key, Value, const N: usize> From<[(Key, Value); N]> for Map<Key, Value>
+where
+    Key: Sort<Key>,
+{
+    #[inline]
+    fn from(fields: [(Key, Value); N]) -> Self {
+        fields.into_iter().collect()
+    }
+}
+
+impl<Key, Value> From<Vec<(Key, Value)>> for Map<Key, Value>
+where
+    Key: Sort<Key>,
+{
+    #[inline]
+    fn from(fields: Vec<(Key, Value)>) -> Self {
+        fields.into_iter().collect()
+    }
+}
+
+impl<Key, Value> From<BTreeMap<Key, Value>> for Map<Key, Value>
+where
+    Key: Ord,
+{
+    /// Converts a `BTreeMap` into a `Map`.
+    ///
+    /// Because a `BTreeMap` iterates its entries in order, no sorting is
+    /// performed.
+    #[inline]
+    fn from(map: BTreeMap<Key, Value>) -> Self {
+        Self::from_sorted_fields(
+            map.into_iter()
+                .map(|(key, value)| Field::new(key, value))
+                .collect(),
+        )
+    }
+}
+
+impl<Key, Value> From<Map<Key, Value>> for BTreeMap<Key, Value>
+where
+    Key: Ord,
+{
+    #[inline]
+    fn from(map: Map<Key, Value>) -> Self {
+        map.into_iter().map(Field::into_parts).collect()
+    }
+}
+
+impl<Key, Value> PartialEq<BTreeMap<Key, Value>> for Map<Key, Value>
+where
+    Key: Sort<Key> + PartialEq,
+    Value: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &BTreeMap<Key, Value>) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .zip(other)
+                .all(|(field, (key, value))| field.key == *key && field.value == *value)
+    }
+}
+
+impl<Key, Value> PartialEq<Map<Key, Value>> for BTreeMap<Key, Value>
+where
+    Key: Sort<Key> + PartialEq,
+    Value: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Map<Key, Value>) -> bool {
+        other == self
     }
 }
 
@@ -843,7 +1784,7 @@ where
     Key: Sort<Key>,
 {
     #[inline]
-    fn new(object: &'a mut Map<Key, Value>, index: usize) -> Self {
+    pub(crate) fn new(object: &'a mut Map<Key, Value>, index: usize) -> Self {
         Self { object, index }
     }
 
@@ -1511,314 +2452,1549 @@ impl<'a, Key, Value> DoubleEndedIterator for Drain<'a, Key, Value> {
 
 impl<'a, Key, Value> FusedIterator for Drain<'a, Key, Value> {}
 
-/// An iterator that yields [`Unioned`] entries for two [`Map`]s.
-///
-/// The iterator will return a single result for each unique `Key` contained in
-/// either map. If both collections contain a key, the iterator will contain
-/// [`Unioned::Both`] for that key.
+/// Walks two sorted sequences of fields that may have different value types,
+/// yielding each key once as a [`EitherOrBoth`] of the fields containing it.
 ///
-/// This iterator is guaranteed to return results in the sort order of the `Key`
-/// type.
-pub struct Union<'a, K, V>
+/// The fields may be owned or borrowed. Each step compares the next key of
+/// each side once.
+struct JoinWalk<Key, LeftValue, RightValue, L, R>
 where
-    K: Sort,
+    L: Iterator,
+    R: Iterator,
 {
-    left: Peekable<Iter<'a, K, V>>,
-    right: Peekable<Iter<'a, K, V>>,
+    left: Peekable<L>,
+    right: Peekable<R>,
+    fields: PhantomData<(Key, LeftValue, RightValue)>,
 }
 
-impl<'a, K, V> Iterator for Union<'a, K, V>
+impl<Key, LeftValue, RightValue, L, R> JoinWalk<Key, LeftValue, RightValue, L, R>
 where
-    K: Sort,
+    L: Iterator,
+    R: Iterator,
 {
-    type Item = Unioned<'a, K, V>;
+    #[inline]
+    fn new(left: L, right: R) -> Self {
+        Self {
+            left: left.peekable(),
+            right: right.peekable(),
+            fields: PhantomData,
+        }
+    }
+}
+
+impl<Key, LeftValue, RightValue, L, R> Iterator for JoinWalk<Key, LeftValue, RightValue, L, R>
+where
+    Key: Sort,
+    L: Iterator,
+    L::Item: Borrow<Field<Key, LeftValue>>,
+    R: Iterator,
+    R::Item: Borrow<Field<Key, RightValue>>,
+{
+    type Item = EitherOrBoth<L::Item, R::Item>;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(left) = self.left.peek() {
-            if let Some(right) = self.right.peek() {
-                match left.key().compare(right.key()) {
-                    Ordering::Less => Some(Unioned::left(self.left.next().expect("just peeked"))),
-                    Ordering::Equal => Some(Unioned::both(
-                        self.left.next().expect("just peeked"),
-                        self.right.next().expect("just peeked"),
-                    )),
-                    Ordering::Greater => {
-                        Some(Unioned::right(self.right.next().expect("just peeked")))
-                    }
-                }
-            } else {
-                Some(Unioned::left(self.left.next().expect("just peeked")))
-            }
-        } else {
-            self.right.next().map(Unioned::right)
+        let ordering = match (self.left.peek(), self.right.peek()) {
+            (Some(left), Some(right)) => left.borrow().key.compare(&right.borrow().key),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => return None,
+        };
+        match ordering {
+            Ordering::Less => self.left.next().map(EitherOrBoth::Left),
+            Ordering::Equal => Some(EitherOrBoth::Both(self.left.next()?, self.right.next()?)),
+            Ordering::Greater => self.right.next().map(EitherOrBoth::Right),
         }
     }
+}
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.left.len(), Some(self.left.len() + self.right.len()))
+/// Completes a [`Map::merge_from`] when dropped, including when unwinding
+/// from a panic, by storing the merged fields followed by any existing fields
+/// that have not been merged yet.
+struct MergeFromGuard<'a, Key, Value> {
+    fields: &'a mut Vec<Field<Key, Value>>,
+    merged: Vec<Field<Key, Value>>,
+    existing: Peekable<vec::IntoIter<Field<Key, Value>>>,
+}
+
+impl<Key, Value> Drop for MergeFromGuard<'_, Key, Value> {
+    fn drop(&mut self) {
+        let mut merged = mem::take(&mut self.merged);
+        merged.extend(&mut self.existing);
+        *self.fields = merged;
     }
 }
 
-/// A unioned entry from a [`Union`] iterator. An entry can be from the left,
-/// right, or both maps.
-pub enum Unioned<'a, K, V> {
-    /// The `self`/left map contained this key/value pair.
-    Left {
-        /// The key of the entry.
-        key: &'a K,
-        /// The value of the entry.
-        value: &'a V,
-    },
-    /// The `other`/right map contained this key/value pair.
-    Right {
-        /// The key of the entry.
-        key: &'a K,
-        /// The value of the entry.
-        value: &'a V,
-    },
-    /// Both maps contained this `key`.
-    Both {
-        /// The key of the entry.
-        key: &'a K,
-        /// The value of the `self`/left entry.
-        left: &'a V,
-        /// The value of the `other`/right entry.
-        right: &'a V,
-    },
+/// An ordered walk over the fields of two [`Map`]s.
+///
+/// Each step compares the first (or last) remaining field of each side,
+/// yielding the lesser (or greater) as a [`Unioned`] entry. Because both sides
+/// are plain slices, the walk can proceed from either end.
+///
+/// When one side is much larger than the other, operations that discard
+/// unmatched fields use a galloping search to skip runs of fields in a single
+/// step. This reduces the cost to `O(m log(n/m))` comparisons, where `m` is the
+/// length of the smaller side.
+///
+/// The right side may contain a different value type, which is used by
+/// [`Map::inner_join`].
+struct MergeWalk<'a, K, V, OtherValue = V> {
+    left: &'a [Field<K, V>],
+    right: &'a [Field<K, OtherValue>],
+    gallop: bool,
 }
 
-impl<'a, K, V> Unioned<'a, K, V> {
-    /// If `self` is [`Unioned::Both`] `merge` will be called to produce a
-    /// single value. If `self` is either [`Unioned::Left`] or
-    /// [`Unioned::Right`], the key/value will be returned without calling
-    /// `merge`.
-    ///
-    /// ```rust
-    /// use kempt::Map;
-    ///
-    /// fn merge(a: &Map<String, u32>, b: &Map<String, u32>) -> Map<String, u32> {
-    ///     a.union(b)
-    ///         .map(|unioned| {
-    ///             unioned
-    ///                 .map_both(|_key, left, right| *left + *right)
-    ///                 .into_owned()
-    ///         })
-    ///         .collect()
-    /// }
-    ///
-    /// let mut a = Map::new();
-    /// a.insert(String::from("a"), 1);
-    /// a.insert(String::from("b"), 1);
-    /// a.insert(String::from("c"), 1);
-    /// let mut b = Map::new();
-    /// b.insert(String::from("b"), 1);
-    ///
-    /// let merged = merge(&a, &b);
-    /// assert_eq!(merged.get("a"), Some(&1));
-    /// assert_eq!(merged.get("b"), Some(&2));
-    /// ```
+impl<'a, K, V, OtherValue> MergeWalk<'a, K, V, OtherValue>
+where
+    K: Sort,
+{
     #[inline]
-    pub fn map_both<R>(self, merge: impl FnOnce(&'a K, &'a V, &'a V) -> R) -> EntryRef<'a, K, V>
-    where
-        R: Into<OwnedOrRef<'a, V>>,
-    {
-        match self {
-            Unioned::Left { key, value } | Unioned::Right { key, value } => EntryRef {
-                key,
-                value: OwnedOrRef::Ref(value),
-            },
-            Unioned::Both { key, left, right } => EntryRef {
-                key,
-                value: merge(key, left, right).into(),
-            },
+    fn new(left: &'a [Field<K, V>], right: &'a [Field<K, OtherValue>]) -> Self {
+        let gallop = prefers_search(left.len().min(right.len()), left.len().max(right.len()));
+        Self {
+            left,
+            right,
+            gallop,
         }
     }
-}
 
-impl<'a, K, V> Unioned<'a, K, V> {
-    fn both(left: &'a Field<K, V>, right: &'a Field<K, V>) -> Self {
-        Self::Both {
-            key: left.key(),
-            left: &left.value,
-            right: &right.value,
+    /// Returns `fields` after skipping at least the first field, and any
+    /// further fields less than `key` if galloping.
+    #[inline]
+    fn skip_front<T>(&self, fields: &'a [Field<K, T>], key: &K) -> &'a [Field<K, T>] {
+        if self.gallop {
+            skip_lesser(fields, key)
+        } else {
+            &fields[1..]
         }
     }
 
-    fn left(field: &'a Field<K, V>) -> Self {
-        Self::Left {
-            key: field.key(),
-            value: &field.value,
+    /// Returns `fields` after skipping at least the last field, and any
+    /// further fields greater than `key` if galloping.
+    #[inline]
+    fn skip_back<T>(&self, fields: &'a [Field<K, T>], key: &K) -> &'a [Field<K, T>] {
+        if self.gallop {
+            skip_greater(fields, key)
+        } else {
+            &fields[..fields.len() - 1]
         }
     }
 
-    fn right(field: &'a Field<K, V>) -> Self {
-        Self::Right {
-            key: field.key(),
-            value: &field.value,
+    /// Returns the next key contained in both sides, skipping all unmatched
+    /// fields.
+    #[inline]
+    fn next_both(&mut self) -> Option<(&'a K, &'a V, &'a OtherValue)> {
+        loop {
+            let (left, left_rest) = self.left.split_first()?;
+            let (right, right_rest) = self.right.split_first()?;
+            match left.key.compare(&right.key) {
+                Ordering::Less => self.left = self.skip_front(self.left, &right.key),
+                Ordering::Equal => {
+                    self.left = left_rest;
+                    self.right = right_rest;
+                    return Some((&left.key, &left.value, &right.value));
+                }
+                Ordering::Greater => self.right = self.skip_front(self.right, &left.key),
+            }
         }
     }
-}
-
-/// A reference to a key from a [`Map`] and an associated value.
-pub struct EntryRef<'a, K, V> {
-    /// The key of the entry.
-    pub key: &'a K,
-    /// The associated value of this key.
-    pub value: OwnedOrRef<'a, V>,
-}
 
-impl<'a, K, V> EntryRef<'a, K, V> {
-    /// Returns the owned versions of the contained key and value, cloning as
-    /// needed.
+    /// Returns the last key contained in both sides, skipping all unmatched
+    /// fields.
     #[inline]
-    pub fn into_owned(self) -> (K, V)
-    where
-        K: Clone,
-        V: Clone,
-    {
-        (self.key.clone(), self.value.into_owned())
+    fn next_both_back(&mut self) -> Option<(&'a K, &'a V, &'a OtherValue)> {
+        loop {
+            let (left, left_rest) = self.left.split_last()?;
+            let (right, right_rest) = self.right.split_last()?;
+            match left.key.compare(&right.key) {
+                Ordering::Greater => self.left = self.skip_back(self.left, &right.key),
+                Ordering::Equal => {
+                    self.left = left_rest;
+                    self.right = right_rest;
+                    return Some((&left.key, &left.value, &right.value));
+                }
+                Ordering::Less => self.right = self.skip_back(self.right, &left.key),
+            }
+        }
     }
-}
 
-/// An owned value or a reference to a value of that type.
-///
-/// This type is similar to [`alloc::borrow::Cow`] except that it does not
-/// require that the contained type implement `ToOwned`.
-pub enum OwnedOrRef<'a, K> {
-    /// An owned value.
-    Owned(K),
-    /// A reference to a value.
-    Ref(&'a K),
-}
+    /// Returns the next field only contained in the left side, skipping all
+    /// other fields.
+    #[inline]
+    fn next_left(&mut self) -> Option<&'a Field<K, V>> {
+        loop {
+            let (left, left_rest) = self.left.split_first()?;
+            let Some((right, right_rest)) = self.right.split_first() else {
+                self.left = left_rest;
+                return Some(left);
+            };
+            match left.key.compare(&right.key) {
+                Ordering::Less => {
+                    self.left = left_rest;
+                    return Some(left);
+                }
+                Ordering::Equal => {
+                    self.left = left_rest;
+                    self.right = right_rest;
+                }
+                Ordering::Greater => self.right = self.skip_front(self.right, &left.key),
+            }
+        }
+    }
 
-impl<'a, K> OwnedOrRef<'a, K> {
-    /// Converts the contained value into an owned representation, cloning only
-    /// if needed.
+    /// Returns the last field only contained in the left side, skipping all
+    /// other fields.
     #[inline]
-    pub fn into_owned(self) -> K
-    where
-        K: Clone,
-    {
-        match self {
-            OwnedOrRef::Owned(owned) => owned,
-            OwnedOrRef::Ref(r) => r.clone(),
+    fn next_left_back(&mut self) -> Option<&'a Field<K, V>> {
+        loop {
+            let (left, left_rest) = self.left.split_last()?;
+            let Some((right, right_rest)) = self.right.split_last() else {
+                self.left = left_rest;
+                return Some(left);
+            };
+            match left.key.compare(&right.key) {
+                Ordering::Greater => {
+                    self.left = left_rest;
+                    return Some(left);
+                }
+                Ordering::Equal => {
+                    self.left = left_rest;
+                    self.right = right_rest;
+                }
+                Ordering::Less => self.right = self.skip_back(self.right, &left.key),
+            }
         }
     }
 }
 
-impl<K> From<K> for OwnedOrRef<'_, K> {
+impl<'a, K, V> MergeWalk<'a, K, V>
+where
+    K: Sort,
+{
     #[inline]
-    fn from(value: K) -> Self {
-        Self::Owned(value)
+    fn next(&mut self) -> Option<Unioned<'a, K, V>> {
+        match (self.left.split_first(), self.right.split_first()) {
+            (Some((left, left_rest)), Some((right, right_rest))) => {
+                match left.key().compare(right.key()) {
+                    Ordering::Less => {
+                        self.left = left_rest;
+                        Some(Unioned::left(left))
+                    }
+                    Ordering::Equal => {
+                        self.left = left_rest;
+                        self.right = right_rest;
+                        Some(Unioned::both(left, right))
+                    }
+                    Ordering::Greater => {
+                        self.right = right_rest;
+                        Some(Unioned::right(right))
+                    }
+                }
+            }
+            (Some((left, left_rest)), None) => {
+                self.left = left_rest;
+                Some(Unioned::left(left))
+            }
+            (None, Some((right, right_rest))) => {
+                self.right = right_rest;
+                Some(Unioned::right(right))
+            }
+            (None, None) => None,
+        }
     }
-}
 
-impl<'a, K> From<&'a K> for OwnedOrRef<'a, K> {
     #[inline]
-    fn from(value: &'a K) -> Self {
-        Self::Ref(value)
+    fn next_back(&mut self) -> Option<Unioned<'a, K, V>> {
+        match (self.left.split_last(), self.right.split_last()) {
+            (Some((left, left_rest)), Some((right, right_rest))) => {
+                match left.key().compare(right.key()) {
+                    Ordering::Greater => {
+                        self.left = left_rest;
+                        Some(Unioned::left(left))
+                    }
+                    Ordering::Equal => {
+                        self.left = left_rest;
+                        self.right = right_rest;
+                        Some(Unioned::both(left, right))
+                    }
+                    Ordering::Less => {
+                        self.right = right_rest;
+                        Some(Unioned::right(right))
+                    }
+                }
+            }
+            (Some((left, left_rest)), None) => {
+                self.left = left_rest;
+                Some(Unioned::left(left))
+            }
+            (None, Some((right, right_rest))) => {
+                self.right = right_rest;
+                Some(Unioned::right(right))
+            }
+            (None, None) => None,
+        }
     }
 }
 
-/// An iterator that yields entries that appear in two maps.
+/// Returns an iterator that merges the fields of all `maps`, yielding each
+/// unique key once along with every value stored for that key.
 ///
-/// The iterator will return a result for each `Key` contained in both maps. If
-/// a particular key is only found in one collection, it will not be included.
+/// Each value is paired with the index of the map it was found in, based on the
+/// order `maps` were provided in. The values for each key are returned in the
+/// order of their source index.
+///
+/// The maps are merged using a binary heap of the maps' next keys, making
+/// this more efficient than repeatedly calling [`Map::union`] when merging many
+/// maps.
+///
+/// This iterator is guaranteed to return results in the sort order of the `Key`
+/// type.
+///
+/// ```rust
+/// use kempt::{map, Map};
+///
+/// let a = Map::from([("a", 1), ("b", 2)]);
+/// let b = Map::from([("b", 3), ("c", 4)]);
+/// let c = Map::from([("a", 5), ("c", 6)]);
+/// let mut merged = map::merge_many([&a, &b, &c]);
+/// assert_eq!(merged.next(), Some((&"a", vec![(0, &1), (2, &5)])));
+/// assert_eq!(merged.next(), Some((&"b", vec![(0, &2), (1, &3)])));
+/// assert_eq!(merged.next(), Some((&"c", vec![(1, &4), (2, &6)])));
+/// assert_eq!(merged.next(), None);
+/// ```
+pub fn merge_many<'a, K, V>(maps: impl IntoIterator<Item = &'a Map<K, V>>) -> MergeMany<'a, K, V>
+where
+    K: Sort + 'a,
+    V: 'a,
+{
+    MergeMany::new(maps.into_iter().map(|map| map.fields.as_slice()).collect())
+}
+
+/// Returns an iterator that yields each key contained in every one of `maps`,
+/// along with the value from each map.
+///
+/// The values for each key are returned in the order `maps` were provided in.
+/// If no maps are provided, the iterator will not return any results.
 ///
 /// This iterator is guaranteed to return results in the sort order of the `Key`
 /// type.
-pub struct Intersection<'a, K, V>
+///
+/// ```rust
+/// use kempt::{map, Map};
+///
+/// let a = Map::from([("a", 1), ("b", 2), ("c", 3)]);
+/// let b = Map::from([("b", 4), ("c", 5)]);
+/// let c = Map::from([("a", 6), ("c", 7)]);
+/// let mut shared = map::intersect_many([&a, &b, &c]);
+/// assert_eq!(shared.next(), Some((&"c", vec![&3, &5, &7])));
+/// assert_eq!(shared.next(), None);
+/// ```
+pub fn intersect_many<'a, K, V>(
+    maps: impl IntoIterator<Item = &'a Map<K, V>>,
+) -> IntersectMany<'a, K, V>
+where
+    K: Sort + 'a,
+    V: 'a,
+{
+    IntersectMany {
+        sources: maps.into_iter().map(|map| map.fields.as_slice()).collect(),
+    }
+}
+
+/// An iterator that merges the fields of many [`Map`]s.
+///
+/// This iterator is returned from [`merge_many()`].
+pub struct MergeMany<'a, K, V>
 where
     K: Sort,
 {
-    left: Peekable<Iter<'a, K, V>>,
-    right: Peekable<Iter<'a, K, V>>,
+    sources: Vec<&'a [Field<K, V>]>,
+    /// The indices of the non-empty `sources`, arranged as a binary min-heap
+    /// ordered by each source's first key. Ties are broken by the source's
+    /// index, which ensures equal keys are visited in source order.
+    heap: Vec<usize>,
 }
 
-impl<'a, K, V> Iterator for Intersection<'a, K, V>
+impl<'a, K, V> MergeMany<'a, K, V>
 where
     K: Sort,
 {
-    type Item = (&'a K, &'a V, &'a V);
+    fn new(sources: Vec<&'a [Field<K, V>]>) -> Self {
+        let heap = (0..sources.len())
+            .filter(|&index| !sources[index].is_empty())
+            .collect::<Vec<_>>();
+        let mut merge = Self { sources, heap };
+        for position in (0..merge.heap.len() / 2).rev() {
+            merge.sift_down(position);
+        }
+        merge
+    }
+
+    #[inline]
+    fn precedes(&self, a: usize, b: usize) -> bool {
+        match self.sources[a][0].key.compare(&self.sources[b][0].key) {
+            Ordering::Less => true,
+            Ordering::Equal => a < b,
+            Ordering::Greater => false,
+        }
+    }
+
+    fn sift_down(&mut self, mut position: usize) {
+        loop {
+            let left = position * 2 + 1;
+            if left >= self.heap.len() {
+                break;
+            }
+            let right = left + 1;
+            let child =
+                if right < self.heap.len() && self.precedes(self.heap[right], self.heap[left]) {
+                    right
+                } else {
+                    left
+                };
+            if self.precedes(self.heap[child], self.heap[position]) {
+                self.heap.swap(child, position);
+                position = child;
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Advances past the next key, invoking `visit` with each value stored for
+    /// that key and its source index.
+    pub(crate) fn advance(&mut self, mut visit: impl FnMut(usize, &'a V)) -> Option<&'a K> {
+        let first: &'a [Field<K, V>] = self.sources[*self.heap.first()?];
+        let key = &first[0].key;
+        while let Some(&source) = self.heap.first() {
+            let fields: &'a [Field<K, V>] = self.sources[source];
+            if fields[0].key.compare(key) != Ordering::Equal {
+                break;
+            }
+
+            visit(source, &fields[0].value);
+            self.sources[source] = &fields[1..];
+            if self.sources[source].is_empty() {
+                self.heap.swap_remove(0);
+            }
+            if !self.heap.is_empty() {
+                self.sift_down(0);
+            }
+        }
+        Some(key)
+    }
+}
+
+impl<'a, K, V> Iterator for MergeMany<'a, K, V>
+where
+    K: Sort,
+{
+    type Item = (&'a K, Vec<(usize, &'a V)>);
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
+        let mut values = Vec::new();
+        let key = self.advance(|source, value| values.push((source, value)))?;
+        Some((key, values))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.heap
+            .iter()
+            .map(|&source| self.sources[source].len())
+            .fold((0, Some(0)), |(min, max), len| {
+                (min.max(len), max.and_then(|max| max.checked_add(len)))
+            })
+    }
+}
+
+impl<K, V> FusedIterator for MergeMany<'_, K, V> where K: Sort {}
+
+/// An iterator that yields the keys contained in every one of many [`Map`]s.
+///
+/// This iterator is returned from [`intersect_many()`].
+pub struct IntersectMany<'a, K, V>
+where
+    K: Sort,
+{
+    sources: Vec<&'a [Field<K, V>]>,
+}
+
+impl<'a, K, V> IntersectMany<'a, K, V>
+where
+    K: Sort,
+{
+    /// Advances past the next key contained in every source, invoking `visit`
+    /// with each source's value in source order.
+    pub(crate) fn advance(&mut self, mut visit: impl FnMut(&'a V)) -> Option<&'a K> {
+        let first: &'a [Field<K, V>] = self.sources.first()?;
+        let mut candidate = &first.first()?.key;
+        // Repeatedly advance each source to the candidate. If a source has no
+        // matching key, its next key becomes the candidate, and the sources
+        // are advanced again. Once every source starts with the candidate, it
+        // is contained in every source.
         loop {
-            let left = self.left.peek()?;
-            let right = self.right.peek()?;
-            match left.key().compare(right.key()) {
-                Ordering::Less => {
-                    let _skipped = self.left.next();
-                }
-                Ordering::Equal => {
-                    let left = self.left.next().expect("just peeked");
-                    let right = self.right.next().expect("just peeked");
-                    return Some((left.key(), &left.value, &right.value));
+            let mut matched = true;
+            for source in &mut self.sources {
+                let fields = skip_lesser(source, candidate);
+                *source = fields;
+                let front = fields.first()?;
+                if front.key.compare(candidate) == Ordering::Greater {
+                    candidate = &front.key;
+                    matched = false;
                 }
-                Ordering::Greater => {
-                    let _skipped = self.right.next();
+            }
+
+            if matched {
+                for source in &mut self.sources {
+                    let fields: &'a [Field<K, V>] = source;
+                    visit(&fields[0].value);
+                    *source = &fields[1..];
                 }
+                return Some(candidate);
             }
         }
     }
+}
+
+impl<'a, K, V> Iterator for IntersectMany<'a, K, V>
+where
+    K: Sort,
+{
+    type Item = (&'a K, Vec<&'a V>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut values = Vec::with_capacity(self.sources.len());
+        let key = self.advance(|value| values.push(value))?;
+        Some((key, values))
+    }
 
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (0, Some(self.left.len().min(self.right.len())))
+        (0, self.sources.iter().map(|source| source.len()).min())
     }
 }
 
-/// An iterator over the difference between two [`Map`]s.
+impl<K, V> FusedIterator for IntersectMany<'_, K, V> where K: Sort {}
+
+/// Returns the portion of `fields` that begins with the first key that is
+/// greater than or equal to `key`.
 ///
-/// This iterator will return a result for each `Key` contained in `self` but
-/// not contained in `other`. If a `Key` is only in `other` or is in both
-/// collections, it will not be returned.
+/// This uses a galloping search, which costs `O(log n)` comparisons where `n` is
+/// the number of fields skipped.
+#[inline]
+fn skip_lesser<'a, K, V>(fields: &'a [Field<K, V>], key: &K) -> &'a [Field<K, V>]
+where
+    K: Sort,
+{
+    &fields[gallop_skip_count(fields, key)..]
+}
+
+/// Returns the number of leading `fields` whose keys are less than `key`.
+#[inline]
+fn gallop_skip_count<K, V>(fields: &[Field<K, V>], key: &K) -> usize
+where
+    K: Sort,
+{
+    gallop(fields, |field| field.key.compare(key) == Ordering::Less)
+}
+
+/// Returns the portion of `fields` that ends with the last key that is less
+/// than or equal to `key`.
 ///
-/// This iterator is guaranteed to return results in the sort order of the `Key`
-/// type.
-pub struct Difference<'a, K, V>
+/// This uses a galloping search from the end of `fields`, which costs
+/// `O(log n)` comparisons where `n` is the number of fields skipped.
+#[inline]
+fn skip_greater<'a, K, V>(fields: &'a [Field<K, V>], key: &K) -> &'a [Field<K, V>]
 where
     K: Sort,
 {
-    left: Peekable<Iter<'a, K, V>>,
-    right: Peekable<Iter<'a, K, V>>,
+    &fields
+        [..fields.len() - gallop_back(fields, |field| field.key.compare(key) == Ordering::Greater)]
 }
 
-impl<'a, K, V> Iterator for Difference<'a, K, V>
+/// Returns the number of leading `items` that match `predicate`, which must
+/// match a prefix of `items`.
+///
+/// This performs an exponential search from the start of `items` followed by a
+/// binary search of the final window.
+fn gallop<T>(items: &[T], mut predicate: impl FnMut(&T) -> bool) -> usize {
+    let mut bound = 1;
+    while bound <= items.len() && predicate(&items[bound - 1]) {
+        bound *= 2;
+    }
+    let start = bound / 2;
+    let end = bound.min(items.len());
+    start + items[start..end].partition_point(predicate)
+}
+
+/// Returns the number of trailing `items` that match `predicate`, which must
+/// match a suffix of `items`.
+///
+/// This performs an exponential search from the end of `items` followed by a
+/// binary search of the final window.
+fn gallop_back<T>(items: &[T], mut predicate: impl FnMut(&T) -> bool) -> usize {
+    let mut bound = 1;
+    while bound <= items.len() && predicate(&items[items.len() - bound]) {
+        bound *= 2;
+    }
+    let start = bound / 2;
+    let end = bound.min(items.len());
+    let window = &items[items.len() - end..items.len() - start];
+    start + window.len() - window.partition_point(|item| !predicate(item))
+}
+
+/// An iterator that yields [`Unioned`] entries for two [`Map`]s.
+///
+/// The iterator will return a single result for each unique `Key` contained in
+/// either map. If both collections contain a key, the iterator will contain
+/// [`Unioned::Both`] for that key.
+///
+/// This iterator is guaranteed to return results in the sort order of the `Key`
+/// type.
+pub struct Union<'a, K, V>(MergeWalk<'a, K, V>)
+where
+    K: Sort;
+
+impl<'a, K, V> Iterator for Union<'a, K, V>
 where
     K: Sort,
 {
-    type Item = (&'a K, &'a V);
+    type Item = Unioned<'a, K, V>;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            let left = self.left.peek()?;
-            if let Some(right) = self.right.peek() {
-                match left.key().compare(right.key()) {
-                    Ordering::Less => {
-                        let left = self.left.next().expect("just peeked");
-                        return Some((left.key(), &left.value));
-                    }
-                    Ordering::Equal => {
-                        let _left = self.left.next();
-                        let _right = self.right.next();
-                    }
-                    Ordering::Greater => {
-                        let _skipped = self.right.next();
-                    }
-                }
-            } else {
-                let left = self.left.next().expect("just peeked");
-                return Some((left.key(), &left.value));
-            }
-        }
+        self.0.next()
     }
 
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (0, Some(self.left.len()))
+        let left = self.0.left.len();
+        let right = self.0.right.len();
+        (left.max(right), Some(left + right))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Union<'_, K, V>
+where
+    K: Sort,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<K, V> FusedIterator for Union<'_, K, V> where K: Sort {}
+
+/// A unioned entry from a [`Union`] iterator. An entry can be from the left,
+/// right, or both maps.
+pub enum Unioned<'a, K, V> {
+    /// The `self`/left map contained this key/value pair.
+    Left {
+        /// The key of the entry.
+        key: &'a K,
+        /// The value of the entry.
+        value: &'a V,
+    },
+    /// The `other`/right map contained this key/value pair.
+    Right {
+        /// The key of the entry.
+        key: &'a K,
+        /// The value of the entry.
+        value: &'a V,
+    },
+    /// Both maps contained this `key`.
+    Both {
+        /// The key of the entry.
+        key: &'a K,
+        /// The value of the `self`/left entry.
+        left: &'a V,
+        /// The value of the `other`/right entry.
+        right: &'a V,
+    },
+}
+
+impl<'a, K, V> Unioned<'a, K, V> {
+    /// If `self` is [`Unioned::Both`] `merge` will be called to produce a
+    /// single value. If `self` is either [`Unioned::Left`] or
+    /// [`Unioned::Right`], the key/value will be returned without calling
+    /// `merge`.
+    ///
+    /// ```rust
+    /// use kempt::Map;
+    ///
+    /// fn merge(a: &Map<String, u32>, b: &Map<String, u32>) -> Map<String, u32> {
+    ///     a.union(b)
+    ///         .map(|unioned| {
+    ///             unioned
+    ///                 .map_both(|_key, left, right| *left + *right)
+    ///                 .into_owned()
+    ///         })
+    ///         .collect()
+    /// }
+    ///
+    /// let mut a = Map::new();
+    /// a.insert(String::from("a"), 1);
+    /// a.insert(String::from("b"), 1);
+    /// a.insert(String::from("c"), 1);
+    /// let mut b = Map::new();
+    /// b.insert(String::from("b"), 1);
+    ///
+    /// let merged = merge(&a, &b);
+    /// assert_eq!(merged.get("a"), Some(&1));
+    /// assert_eq!(merged.get("b"), Some(&2));
+    /// ```
+    #[inline]
+    pub fn map_both<R>(self, merge: impl FnOnce(&'a K, &'a V, &'a V) -> R) -> EntryRef<'a, K, V>
+    where
+        R: Into<OwnedOrRef<'a, V>>,
+    {
+        match self {
+            Unioned::Left { key, value } | Unioned::Right { key, value } => EntryRef {
+                key,
+                value: OwnedOrRef::Ref(value),
+            },
+            Unioned::Both { key, left, right } => EntryRef {
+                key,
+                value: merge(key, left, right).into(),
+            },
+        }
+    }
+}
+
+impl<K, V> Clone for Unioned<'_, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> Copy for Unioned<'_, K, V> {}
+
+impl<'a, K, V> Unioned<'a, K, V> {
+    fn both(left: &'a Field<K, V>, right: &'a Field<K, V>) -> Self {
+        Self::Both {
+            key: left.key(),
+            left: &left.value,
+            right: &right.value,
+        }
+    }
+
+    fn left(field: &'a Field<K, V>) -> Self {
+        Self::Left {
+            key: field.key(),
+            value: &field.value,
+        }
+    }
+
+    fn right(field: &'a Field<K, V>) -> Self {
+        Self::Right {
+            key: field.key(),
+            value: &field.value,
+        }
+    }
+}
+
+/// A reference to a key from a [`Map`] and an associated value.
+pub struct EntryRef<'a, K, V> {
+    /// The key of the entry.
+    pub key: &'a K,
+    /// The associated value of this key.
+    pub value: OwnedOrRef<'a, V>,
+}
+
+impl<'a, K, V> EntryRef<'a, K, V> {
+    /// Returns the owned versions of the contained key and value, cloning as
+    /// needed.
+    #[inline]
+    pub fn into_owned(self) -> (K, V)
+    where
+        K: Clone,
+        V: Clone,
+    {
+        (self.key.clone(), self.value.into_owned())
+    }
+}
+
+/// An owned value or a reference to a value of that type.
+///
+/// This type is similar to [`alloc::borrow::Cow`] except that it does not
+/// require that the contained type implement `ToOwned`.
+pub enum OwnedOrRef<'a, K> {
+    /// An owned value.
+    Owned(K),
+    /// A reference to a value.
+    Ref(&'a K),
+}
+
+impl<'a, K> OwnedOrRef<'a, K> {
+    /// Converts the contained value into an owned representation, cloning only
+    /// if needed.
+    #[inline]
+    pub fn into_owned(self) -> K
+    where
+        K: Clone,
+    {
+        match self {
+            OwnedOrRef::Owned(owned) => owned,
+            OwnedOrRef::Ref(r) => r.clone(),
+        }
+    }
+}
+
+impl<K> From<K> for OwnedOrRef<'_, K> {
+    #[inline]
+    fn from(value: K) -> Self {
+        Self::Owned(value)
+    }
+}
+
+impl<'a, K> From<&'a K> for OwnedOrRef<'a, K> {
+    #[inline]
+    fn from(value: &'a K) -> Self {
+        Self::Ref(value)
+    }
+}
+
+/// An iterator that yields entries that appear in two maps.
+///
+/// The iterator will return a result for each `Key` contained in both maps. If
+/// a particular key is only found in one collection, it will not be included.
+///
+/// This iterator is guaranteed to return results in the sort order of the `Key`
+/// type.
+pub struct Intersection<'a, K, V>(MergeWalk<'a, K, V>)
+where
+    K: Sort;
+
+impl<'a, K, V> Iterator for Intersection<'a, K, V>
+where
+    K: Sort,
+{
+    type Item = (&'a K, &'a V, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_both()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.0.left.len().min(self.0.right.len())))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Intersection<'_, K, V>
+where
+    K: Sort,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_both_back()
+    }
+}
+
+impl<K, V> FusedIterator for Intersection<'_, K, V> where K: Sort {}
+
+/// An iterator that yields entries that appear in two maps, with exclusive
+/// access to the values of the first map.
+///
+/// The iterator will return a result for each `Key` contained in both maps. If
+/// a particular key is only found in one collection, it will not be included.
+///
+/// This iterator is guaranteed to return results in the sort order of the `Key`
+/// type.
+pub struct IntersectionMut<'a, K, V, OtherValue>
+where
+    K: Sort,
+{
+    left: &'a mut [Field<K, V>],
+    right: &'a [Field<K, OtherValue>],
+    gallop: bool,
+}
+
+impl<'a, K, V, OtherValue> Iterator for IntersectionMut<'a, K, V, OtherValue>
+where
+    K: Sort,
+{
+    type Item = (&'a K, &'a mut V, &'a OtherValue);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let left_key = &self.left.first()?.key;
+            let right = self.right.first()?;
+            match left_key.compare(&right.key) {
+                Ordering::Less => {
+                    let skip = if self.gallop {
+                        gallop_skip_count(self.left, &right.key)
+                    } else {
+                        1
+                    };
+                    self.left = &mut mem::take(&mut self.left)[skip..];
+                }
+                Ordering::Equal => {
+                    let (left, left_rest) = mem::take(&mut self.left).split_first_mut()?;
+                    self.left = left_rest;
+                    self.right = &self.right[1..];
+                    return Some((&left.key, &mut left.value, &right.value));
+                }
+                Ordering::Greater => {
+                    self.right = if self.gallop {
+                        skip_lesser(self.right, left_key)
+                    } else {
+                        &self.right[1..]
+                    };
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.left.len().min(self.right.len())))
+    }
+}
+
+impl<K, V, OtherValue> DoubleEndedIterator for IntersectionMut<'_, K, V, OtherValue>
+where
+    K: Sort,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            let left_key = &self.left.last()?.key;
+            let right = self.right.last()?;
+            match left_key.compare(&right.key) {
+                Ordering::Greater => {
+                    let keep = if self.gallop {
+                        skip_greater(self.left, &right.key).len()
+                    } else {
+                        self.left.len() - 1
+                    };
+                    self.left = &mut mem::take(&mut self.left)[..keep];
+                }
+                Ordering::Equal => {
+                    let (left, left_rest) = mem::take(&mut self.left).split_last_mut()?;
+                    self.left = left_rest;
+                    self.right = &self.right[..self.right.len() - 1];
+                    return Some((&left.key, &mut left.value, &right.value));
+                }
+                Ordering::Less => {
+                    self.right = if self.gallop {
+                        skip_greater(self.right, left_key)
+                    } else {
+                        &self.right[..self.right.len() - 1]
+                    };
+                }
+            }
+        }
+    }
+}
+
+impl<K, V, OtherValue> FusedIterator for IntersectionMut<'_, K, V, OtherValue> where K: Sort {}
+
+/// An iterator over the difference between two [`Map`]s.
+///
+/// This iterator will return a result for each `Key` contained in `self` but
+/// not contained in `other`. If a `Key` is only in `other` or is in both
+/// collections, it will not be returned.
+///
+/// This iterator is guaranteed to return results in the sort order of the `Key`
+/// type.
+pub struct Difference<'a, K, V>(MergeWalk<'a, K, V>)
+where
+    K: Sort;
+
+impl<'a, K, V> Iterator for Difference<'a, K, V>
+where
+    K: Sort,
+{
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_left().map(|field| (&field.key, &field.value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.0.left.len();
+        let right = self.0.right.len();
+        (left.saturating_sub(right), Some(left))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Difference<'_, K, V>
+where
+    K: Sort,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0
+            .next_left_back()
+            .map(|field| (&field.key, &field.value))
+    }
+}
+
+impl<K, V> FusedIterator for Difference<'_, K, V> where K: Sort {}
+
+/// An iterator over the symmetric difference between two [`Map`]s.
+///
+/// This iterator will return [`Unioned::Left`] for each `Key` only contained in
+/// `self` and [`Unioned::Right`] for each `Key` only contained in `other`. If a
+/// `Key` is in both collections, it will not be returned.
+///
+/// This iterator is guaranteed to return results in the sort order of the `Key`
+/// type.
+pub struct SymmetricDifference<'a, K, V>(MergeWalk<'a, K, V>)
+where
+    K: Sort;
+
+impl<'a, K, V> Iterator for SymmetricDifference<'a, K, V>
+where
+    K: Sort,
+{
+    type Item = Unioned<'a, K, V>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.0.next()? {
+                Unioned::Both { .. } => {}
+                unioned => return Some(unioned),
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.0.left.len();
+        let right = self.0.right.len();
+        (left.abs_diff(right), Some(left + right))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for SymmetricDifference<'_, K, V>
+where
+    K: Sort,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.0.next_back()? {
+                Unioned::Both { .. } => {}
+                unioned => return Some(unioned),
+            }
+        }
+    }
+}
+
+impl<K, V> FusedIterator for SymmetricDifference<'_, K, V> where K: Sort {}
+
+/// An iterator over the [`Change`]s between two [`Map`]s.
+///
+/// This iterator is returned from [`Map::diff`]. It is guaranteed to return
+/// results in the sort order of the `Key` type.
+pub struct Diff<'a, K, V>(MergeWalk<'a, K, V>)
+where
+    K: Sort;
+
+impl<'a, K, V> Diff<'a, K, V>
+where
+    K: Sort,
+    V: PartialEq,
+{
+    #[inline]
+    fn change(unioned: Unioned<'a, K, V>) -> Option<Change<'a, K, V>> {
+        match unioned {
+            Unioned::Left { key, value } => Some(Change::Removed { key, value }),
+            Unioned::Right { key, value } => Some(Change::Added { key, value }),
+            Unioned::Both { key, left, right } => (left != right).then_some(Change::Modified {
+                key,
+                old: left,
+                new: right,
+            }),
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Diff<'a, K, V>
+where
+    K: Sort,
+    V: PartialEq,
+{
+    type Item = Change<'a, K, V>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(change) = Self::change(self.0.next()?) {
+                return Some(change);
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.0.left.len();
+        let right = self.0.right.len();
+        (left.abs_diff(right), Some(left + right))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Diff<'_, K, V>
+where
+    K: Sort,
+    V: PartialEq,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(change) = Self::change(self.0.next_back()?) {
+                return Some(change);
+            }
+        }
+    }
+}
+
+impl<K, V> FusedIterator for Diff<'_, K, V>
+where
+    K: Sort,
+    V: PartialEq,
+{
+}
+
+/// A key that was changed differently by both sides of a
+/// [three-way merge](Map::merge3).
+#[derive(Debug, Eq, PartialEq)]
+pub enum Conflict<'a, V> {
+    /// Both sides changed the base value to different values.
+    EditEdit {
+        /// The value in the base map.
+        base: &'a V,
+        /// The value in our map.
+        ours: &'a V,
+        /// The value in their map.
+        theirs: &'a V,
+    },
+    /// Our side changed the base value, while their side removed the key.
+    EditDelete {
+        /// The value in the base map.
+        base: &'a V,
+        /// The value in our map.
+        ours: &'a V,
+    },
+    /// Our side removed the key, while their side changed the base value.
+    DeleteEdit {
+        /// The value in the base map.
+        base: &'a V,
+        /// The value in their map.
+        theirs: &'a V,
+    },
+    /// Both sides added the key with different values.
+    AddAdd {
+        /// The value in our map.
+        ours: &'a V,
+        /// The value in their map.
+        theirs: &'a V,
+    },
+}
+
+impl<V> Clone for Conflict<'_, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V> Copy for Conflict<'_, V> {}
+
+/// The outcome of resolving a [`Conflict`] during a
+/// [three-way merge](Map::merge3).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Resolution<V> {
+    /// The key is stored in the merged map with this value.
+    Keep(V),
+    /// The key is not stored in the merged map.
+    Remove,
+    /// The conflict could not be resolved. The merge will fail and return
+    /// this conflict.
+    Unresolved,
+}
+
+/// The values found for a single key when [joining](Map::outer_join) two
+/// [`Map`]s.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum EitherOrBoth<L, R> {
+    /// Only the `self`/left map contained the key.
+    Left(L),
+    /// Only the `other`/right map contained the key.
+    Right(R),
+    /// Both maps contained the key.
+    Both(L, R),
+}
+
+impl<L, R> EitherOrBoth<L, R> {
+    /// Returns the left value, if present.
+    #[must_use]
+    #[inline]
+    pub fn left(&self) -> Option<&L> {
+        match self {
+            EitherOrBoth::Left(left) | EitherOrBoth::Both(left, _) => Some(left),
+            EitherOrBoth::Right(_) => None,
+        }
+    }
+
+    /// Returns the right value, if present.
+    #[must_use]
+    #[inline]
+    pub fn right(&self) -> Option<&R> {
+        match self {
+            EitherOrBoth::Right(right) | EitherOrBoth::Both(_, right) => Some(right),
+            EitherOrBoth::Left(_) => None,
+        }
+    }
+
+    /// Returns true if both values are present.
+    #[must_use]
+    #[inline]
+    pub const fn is_both(&self) -> bool {
+        matches!(self, EitherOrBoth::Both(..))
+    }
+
+    /// Returns an `EitherOrBoth` containing references to the contained
+    /// values.
+    #[must_use]
+    #[inline]
+    pub const fn as_ref(&self) -> EitherOrBoth<&L, &R> {
+        match self {
+            EitherOrBoth::Left(left) => EitherOrBoth::Left(left),
+            EitherOrBoth::Right(right) => EitherOrBoth::Right(right),
+            EitherOrBoth::Both(left, right) => EitherOrBoth::Both(left, right),
+        }
+    }
+
+    /// Returns the left and right values as options.
+    #[must_use]
+    #[inline]
+    pub fn into_parts(self) -> (Option<L>, Option<R>) {
+        match self {
+            EitherOrBoth::Left(left) => (Some(left), None),
+            EitherOrBoth::Right(right) => (None, Some(right)),
+            EitherOrBoth::Both(left, right) => (Some(left), Some(right)),
+        }
+    }
+}
+
+impl<L, R> EitherOrBoth<&L, &R> {
+    /// Returns an `EitherOrBoth` containing clones of the referenced values.
+    #[must_use]
+    #[inline]
+    pub fn cloned(self) -> EitherOrBoth<L, R>
+    where
+        L: Clone,
+        R: Clone,
+    {
+        match self {
+            EitherOrBoth::Left(left) => EitherOrBoth::Left(left.clone()),
+            EitherOrBoth::Right(right) => EitherOrBoth::Right(right.clone()),
+            EitherOrBoth::Both(left, right) => EitherOrBoth::Both(left.clone(), right.clone()),
+        }
+    }
+}
+
+/// A difference for a single key between two [`Map`]s.
+///
+/// This type is returned from the [`Diff`] iterator.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Change<'a, K, V> {
+    /// The key was only contained in the `other`/new map.
+    Added {
+        /// The key of the entry.
+        key: &'a K,
+        /// The value of the entry.
+        value: &'a V,
+    },
+    /// The key was only contained in the `self`/old map.
+    Removed {
+        /// The key of the entry.
+        key: &'a K,
+        /// The value of the entry.
+        value: &'a V,
+    },
+    /// The key was contained in both maps, but the values were not equal.
+    Modified {
+        /// The key of the entry.
+        key: &'a K,
+        /// The value of the `self`/old entry.
+        old: &'a V,
+        /// The value of the `other`/new entry.
+        new: &'a V,
+    },
+}
+
+impl<'a, K, V> Change<'a, K, V> {
+    /// Returns the key this change is for.
+    #[must_use]
+    #[inline]
+    pub fn key(&self) -> &'a K {
+        match self {
+            Change::Added { key, .. }
+            | Change::Removed { key, .. }
+            | Change::Modified { key, .. } => key,
+        }
+    }
+
+    /// Returns an owned [`Edit`] describing this change, cloning the values.
+    #[must_use]
+    #[inline]
+    pub fn to_edit(&self) -> Edit<V>
+    where
+        V: Clone,
+    {
+        match self {
+            Change::Added { value, .. } => Edit::Insert((*value).clone()),
+            Change::Removed { value, .. } => Edit::Remove((*value).clone()),
+            Change::Modified { old, new, .. } => Edit::Replace {
+                old: (*old).clone(),
+                new: (*new).clone(),
+            },
+        }
+    }
+}
+
+/// An owned set of changes that can be applied to a [`Map`].
+///
+/// A patch is usually created by collecting the changes returned from
+/// [`Map::diff`]. Each [`Edit`] stores the values from both maps, which allows
+/// the patch to be [inverted](Self::inverse) to undo its changes.
+///
+/// ```rust
+/// use kempt::map::MapPatch;
+/// use kempt::Map;
+///
+/// let old: Map<&'static str, usize> = [("a", 1), ("b", 2)].into_iter().collect();
+/// let new: Map<&'static str, usize> = [("b", 3), ("c", 4)].into_iter().collect();
+/// let patch = old.diff(&new).collect::<MapPatch<_, _>>();
+///
+/// let mut patched = old.clone();
+/// patch.apply(&mut patched);
+/// assert_eq!(patched, new);
+///
+/// patch.inverse().apply(&mut patched);
+/// assert_eq!(patched, old);
+/// ```
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct MapPatch<K, V>
+where
+    K: Sort,
+{
+    pub(crate) edits: Map<K, Edit<V>>,
+}
+
+impl<K, V> Default for MapPatch<K, V>
+where
+    K: Sort,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> MapPatch<K, V>
+where
+    K: Sort,
+{
+    /// Returns an empty patch.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { edits: Map::new() }
+    }
+
+    /// Returns the number of keys changed by this patch.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.edits.len()
+    }
+
+    /// Returns true if this patch makes no changes.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    /// Returns the edit for `key`, if this patch changes it.
+    #[inline]
+    pub fn get<SearchFor>(&self, key: &SearchFor) -> Option<&Edit<V>>
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.edits.get(key)
+    }
+
+    /// Records `edit` for `key`, returning the edit previously recorded for
+    /// `key`, if any.
+    #[inline]
+    pub fn insert(&mut self, key: K, edit: Edit<V>) -> Option<Edit<V>> {
+        self.edits.insert(key, edit).map(|field| field.value)
+    }
+
+    /// Returns an iterator over the edits in this patch.
+    #[must_use]
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, Edit<V>> {
+        self.edits.iter()
+    }
+
+    /// Returns the inverse of this patch, which undoes the changes this patch
+    /// describes.
+    ///
+    /// Applying a patch and then applying its inverse restores the original
+    /// map.
+    #[must_use]
+    pub fn inverse(self) -> Self {
+        Self {
+            edits: Map::from_sorted_fields(
+                self.edits
+                    .into_iter()
+                    .map(|field| {
+                        let (key, edit) = field.into_parts();
+                        Field::new(key, edit.inverse())
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Applies the edits in this patch to `map`, cloning the keys and values
+    /// as needed.
+    ///
+    /// The old values recorded in each edit are not checked against `map`:
+    /// [`Edit::Insert`] and [`Edit::Replace`] store the new value regardless of
+    /// whether `map` contains the key, and [`Edit::Remove`] removes the key if
+    /// it is present.
+    ///
+    /// Because both collections are sorted, this is performed in a single pass
+    /// over `map` without shifting its fields for each edit.
+    pub fn apply(&self, map: &mut Map<K, V>)
+    where
+        K: Clone,
+        V: Clone,
+    {
+        let mut existing = mem::take(&mut map.fields).into_iter().peekable();
+        let mut merged = Vec::with_capacity(existing.len() + self.edits.len());
+        for edit in &self.edits {
+            while let Some(field) =
+                existing.next_if(|field| field.key.compare(&edit.key) == Ordering::Less)
+            {
+                merged.push(field);
+            }
+            let current = existing.next_if(|field| field.key.compare(&edit.key) == Ordering::Equal);
+            if let Some(value) = edit.value.new_value() {
+                let key = current.map_or_else(|| edit.key.clone(), Field::into_key);
+                merged.push(Field::new(key, value.clone()));
+            }
+        }
+        merged.extend(existing);
+        map.fields = merged;
+    }
+}
+
+impl<K, V> Debug for MapPatch<K, V>
+where
+    K: Debug + Sort,
+    V: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.edits, f)
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a MapPatch<K, V>
+where
+    K: Sort,
+{
+    type IntoIter = Iter<'a, K, Edit<V>>;
+    type Item = &'a Field<K, Edit<V>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V> FromIterator<Change<'a, K, V>> for MapPatch<K, V>
+where
+    K: Sort + Clone + 'a,
+    V: Clone + 'a,
+{
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = Change<'a, K, V>>>(iter: T) -> Self {
+        Self {
+            edits: iter
+                .into_iter()
+                .map(|change| (change.key().clone(), change.to_edit()))
+                .collect(),
+        }
+    }
+}
+
+/// A change to a single key stored in a [`MapPatch`].
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Edit<V> {
+    /// The key is inserted with the contained value.
+    Insert(V),
+    /// The key, which contained the value, is removed.
+    Remove(V),
+    /// The key's value is replaced.
+    Replace {
+        /// The value being replaced.
+        old: V,
+        /// The value replacing `old`.
+        new: V,
+    },
+}
+
+impl<V> Edit<V> {
+    /// Returns the value before this edit is applied, if the key was present.
+    #[must_use]
+    #[inline]
+    pub fn old_value(&self) -> Option<&V> {
+        match self {
+            Edit::Insert(_) => None,
+            Edit::Remove(old) | Edit::Replace { old, .. } => Some(old),
+        }
+    }
+
+    /// Returns the value after this edit is applied, if the key is present.
+    #[must_use]
+    #[inline]
+    pub fn new_value(&self) -> Option<&V> {
+        match self {
+            Edit::Remove(_) => None,
+            Edit::Insert(new) | Edit::Replace { new, .. } => Some(new),
+        }
+    }
+
+    /// Returns the inverse of this edit, which undoes the change this edit
+    /// describes.
+    #[must_use]
+    #[inline]
+    pub fn inverse(self) -> Self {
+        match self {
+            Edit::Insert(value) => Edit::Remove(value),
+            Edit::Remove(value) => Edit::Insert(value),
+            Edit::Replace { old, new } => Edit::Replace { old: new, new: old },
+        }
     }
 }
@@ -0,0 +1,446 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Debug};
+use core::iter::FusedIterator;
+use core::ops::RangeBounds;
+use core::slice;
+
+use crate::map::{self, Field};
+use crate::{Map, Sort};
+
+/// An iterator over the groups of values in a [`MultiMap`].
+pub type Groups<'a, K, V> = map::Iter<'a, K, Vec<V>>;
+/// An iterator over the unique keys in a [`MultiMap`].
+pub type Keys<'a, K, V> = map::Keys<'a, K, Vec<V>>;
+
+/// An ordered collection that can store several values for each key.
+///
+/// This type is similar to a `Map<K, Vec<V>>`, but handles appending to and
+/// removing from each key's values. Keys are stored in sort order, and each
+/// key's values are stored in the order they were inserted.
+///
+/// ```rust
+/// use kempt::MultiMap;
+///
+/// let mut map = MultiMap::new();
+/// map.insert("a", 1);
+/// map.insert("b", 2);
+/// map.insert("a", 3);
+/// assert_eq!(map.get_all(&"a"), &[1, 3]);
+/// assert_eq!(map.len(), 3);
+/// assert_eq!(map.key_count(), 2);
+/// assert!(map.iter().eq([(&"a", &1), (&"a", &3), (&"b", &2)]));
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct MultiMap<K, V>
+where
+    K: Sort<K>,
+{
+    groups: Map<K, Vec<V>>,
+    len: usize,
+}
+
+impl<K, V> Default for MultiMap<K, V>
+where
+    K: Sort<K>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> MultiMap<K, V>
+where
+    K: Sort<K>,
+{
+    /// Returns an empty map.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            groups: Map::new(),
+            len: 0,
+        }
+    }
+
+    /// Returns a map containing `values` grouped by the key returned from
+    /// `key_fn`.
+    ///
+    /// The values for each key are stored in the order they were returned from
+    /// `values`.
+    ///
+    /// ```rust
+    /// use kempt::MultiMap;
+    ///
+    /// let by_length = MultiMap::group_by(["one", "two", "three", "four"], |word| word.len());
+    /// assert_eq!(by_length.get_all(&3), &["one", "two"]);
+    /// assert_eq!(by_length.get_all(&4), &["four"]);
+    /// assert_eq!(by_length.get_all(&5), &["three"]);
+    /// ```
+    #[inline]
+    pub fn group_by(values: impl IntoIterator<Item = V>, mut key_fn: impl FnMut(&V) -> K) -> Self {
+        values
+            .into_iter()
+            .map(|value| (key_fn(&value), value))
+            .collect()
+    }
+
+    /// Appends `value` to the values stored for `key`.
+    #[inline]
+    pub fn insert(&mut self, key: K, value: V) {
+        self.groups.get_or_insert_with(key, Vec::new).push(value);
+        self.len += 1;
+    }
+
+    /// Returns true if this map contains any values for `key`.
+    #[inline]
+    pub fn contains_key<SearchFor>(&self, key: &SearchFor) -> bool
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.groups.contains(key)
+    }
+
+    /// Returns the values stored for `key`, in the order they were inserted.
+    /// If no values are stored for `key`, an empty slice is returned.
+    #[inline]
+    pub fn get_all<SearchFor>(&self, key: &SearchFor) -> &[V]
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.groups.get(key).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns exclusive access to the values stored for `key`, in the order
+    /// they were inserted. If no values are stored for `key`, an empty slice
+    /// is returned.
+    #[inline]
+    pub fn get_all_mut<SearchFor>(&mut self, key: &SearchFor) -> &mut [V]
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.groups.get_mut(key).map_or(&mut [], Vec::as_mut_slice)
+    }
+
+    /// Removes all values stored for `key`, returning them in the order they
+    /// were inserted. Returns `None` if no values were stored for `key`.
+    #[inline]
+    pub fn remove_all<SearchFor>(&mut self, key: &SearchFor) -> Option<Vec<V>>
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        let removed = self.groups.remove(key)?.value;
+        self.len -= removed.len();
+        Some(removed)
+    }
+
+    /// Removes the first value stored for `key` that is equal to `value`,
+    /// returning the removed value. If the last value for `key` is removed,
+    /// the key is removed as well.
+    ///
+    /// ```rust
+    /// use kempt::MultiMap;
+    ///
+    /// let mut map = MultiMap::from_iter([("a", 1), ("a", 2), ("a", 1)]);
+    /// assert_eq!(map.remove_one(&"a", &1), Some(1));
+    /// assert_eq!(map.get_all(&"a"), &[2, 1]);
+    /// assert_eq!(map.remove_one(&"a", &3), None);
+    /// ```
+    #[inline]
+    pub fn remove_one<SearchFor>(&mut self, key: &SearchFor, value: &V) -> Option<V>
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+        V: PartialEq,
+    {
+        let values = self.groups.get_mut(key)?;
+        let index = values.iter().position(|stored| stored == value)?;
+        let removed = values.remove(index);
+        if values.is_empty() {
+            self.groups.remove(key);
+        }
+        self.len -= 1;
+        Some(removed)
+    }
+
+    /// Returns the total number of values stored in this map.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the number of unique keys stored in this map.
+    #[must_use]
+    #[inline]
+    pub fn key_count(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Returns true if there are no values stored in this map.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator over every key and value stored in this map.
+    ///
+    /// This iterator is guaranteed to return results in the sort order of the
+    /// `Key` type. Values for the same key are returned in the order they were
+    /// inserted.
+    #[must_use]
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.into_iter()
+    }
+
+    /// Returns an iterator over every key and value stored for keys contained
+    /// in `range`.
+    ///
+    /// If the start of `range` is greater than its end, the returned iterator
+    /// will be empty.
+    ///
+    /// ```rust
+    /// use kempt::MultiMap;
+    ///
+    /// let map = MultiMap::from_iter([(1, 'a'), (2, 'b'), (2, 'c'), (3, 'd')]);
+    /// assert!(map.range(2..).eq([(&2, &'b'), (&2, &'c'), (&3, &'d')]));
+    /// ```
+    #[inline]
+    pub fn range<SearchFor, R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        K: Sort<SearchFor>,
+        R: RangeBounds<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        Iter::new(self.groups.range(range))
+    }
+
+    /// Returns an iterator over each unique key and the values stored for it.
+    ///
+    /// This iterator is guaranteed to return results in the sort order of the
+    /// `Key` type.
+    ///
+    /// ```rust
+    /// use kempt::MultiMap;
+    ///
+    /// let map = MultiMap::from_iter([(1, 'a'), (2, 'b'), (2, 'c')]);
+    /// let mut groups = map.groups();
+    /// let group = groups.next().unwrap();
+    /// assert_eq!((group.key(), group.value.as_slice()), (&1, &['a'][..]));
+    /// let group = groups.next().unwrap();
+    /// assert_eq!((group.key(), group.value.as_slice()), (&2, &['b', 'c'][..]));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn groups(&self) -> Groups<'_, K, V> {
+        self.groups.iter()
+    }
+
+    /// Returns an iterator over the unique keys stored in this map.
+    #[must_use]
+    #[inline]
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        self.groups.keys()
+    }
+
+    /// Clears the contents of this collection.
+    ///
+    /// This does not return any allocated memory to the OS.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.groups.clear();
+        self.len = 0;
+    }
+}
+
+impl<K, V> Debug for MultiMap<K, V>
+where
+    K: Sort<K> + Debug,
+    V: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_map();
+        for group in &self.groups {
+            s.entry(group.key(), &group.value);
+        }
+        s.finish()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a MultiMap<K, V>
+where
+    K: Sort<K>,
+{
+    type IntoIter = Iter<'a, K, V>;
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        Iter::new(self.groups.iter())
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for MultiMap<K, V>
+where
+    K: Sort<K>,
+{
+    /// Collects `iter` into a map, sorting the entries once rather than
+    /// inserting each entry individually. Values for the same key are stored in
+    /// the order they were returned from `iter`.
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut entries = iter.into_iter().collect::<Vec<_>>();
+        // The sort is stable, which preserves the order of each key's values.
+        entries.sort_by(|(a, _), (b, _)| a.compare(b));
+
+        let len = entries.len();
+        let mut groups = Vec::<Field<K, Vec<V>>>::new();
+        for (key, value) in entries {
+            match groups.last_mut() {
+                Some(group) if group.key().compare(&key).is_eq() => group.value.push(value),
+                _ => groups.push(Field::new(key, alloc::vec![value])),
+            }
+        }
+        Self {
+            groups: Map::from_sorted_fields(groups),
+            len,
+        }
+    }
+}
+
+impl<K, V> Extend<(K, V)> for MultiMap<K, V>
+where
+    K: Sort<K>,
+{
+    #[inline]
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+/// An iterator over the keys and values in a [`MultiMap`].
+///
+/// This iterator is guaranteed to return results in the sort order of the `Key`
+/// type. Values for the same key are returned in the order they were inserted.
+pub struct Iter<'a, K, V> {
+    groups: map::Iter<'a, K, Vec<V>>,
+    current: Option<(&'a K, slice::Iter<'a, V>)>,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    #[inline]
+    fn new(groups: map::Iter<'a, K, Vec<V>>) -> Self {
+        Self {
+            groups,
+            current: None,
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((key, values)) = &mut self.current {
+                if let Some(value) = values.next() {
+                    return Some((*key, value));
+                }
+            }
+            let group = self.groups.next()?;
+            self.current = Some((group.key(), group.value.iter()));
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let current = self.current.as_ref().map_or(0, |(_, values)| values.len());
+        // Every remaining group contains at least one value.
+        let remaining_groups = self.groups.len();
+        (
+            current + remaining_groups,
+            (remaining_groups == 0).then_some(current),
+        )
+    }
+}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+#[test]
+fn basics() {
+    let mut map = MultiMap::default();
+    assert!(map.is_empty());
+    map.insert(2, 'b');
+    map.insert(1, 'a');
+    map.insert(2, 'c');
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.key_count(), 2);
+    assert!(map.contains_key(&2));
+    assert!(!map.contains_key(&3));
+    assert_eq!(map.get_all(&2), &['b', 'c']);
+    assert!(map.get_all(&3).is_empty());
+
+    map.get_all_mut(&2)[0] = 'B';
+    assert!(map.get_all_mut(&3).is_empty());
+    assert!(map.keys().copied().eq([1, 2]));
+
+    assert_eq!(map.remove_one(&2, &'c'), Some('c'));
+    assert_eq!(map.remove_one(&2, &'c'), None);
+    assert_eq!(map.remove_one(&3, &'c'), None);
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.remove_one(&2, &'B'), Some('B'));
+    assert!(!map.contains_key(&2));
+    assert_eq!(map.key_count(), 1);
+
+    map.insert(1, 'z');
+    assert_eq!(map.remove_all(&1), Some(alloc::vec!['a', 'z']));
+    assert_eq!(map.remove_all(&1), None);
+    assert!(map.is_empty());
+
+    map.extend([(1, 'a'), (1, 'b')]);
+    assert_eq!(map.len(), 2);
+    map.clear();
+    assert!(map.is_empty());
+    assert_eq!(map.key_count(), 0);
+}
+
+#[test]
+fn iteration() {
+    use alloc::format;
+
+    let map = MultiMap::from_iter([(3, 'd'), (1, 'a'), (2, 'b'), (1, 'c')]);
+    assert_eq!(map.len(), 4);
+    assert!(map.get_all(&1).iter().eq(&['a', 'c']));
+
+    let mut iter = map.iter();
+    assert_eq!(iter.size_hint(), (3, None));
+    assert_eq!(iter.next(), Some((&1, &'a')));
+    assert_eq!(iter.size_hint(), (3, None));
+    assert!(iter.eq([(&1, &'c'), (&2, &'b'), (&3, &'d')]));
+    let mut iter = map.range(3..);
+    assert_eq!(iter.next(), Some((&3, &'d')));
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+    assert_eq!(iter.next(), None);
+
+    assert!(map
+        .groups()
+        .map(|group| (*group.key(), group.value.len()))
+        .eq([(1, 2), (2, 1), (3, 1)]));
+    assert_eq!(format!("{map:?}"), "{1: ['a', 'c'], 2: ['b'], 3: ['d']}");
+
+    let grouped = MultiMap::group_by(0..10, |value| value % 3);
+    assert_eq!(grouped.get_all(&0), &[0, 3, 6, 9]);
+    assert_eq!(grouped.get_all(&1), &[1, 4, 7]);
+    assert_eq!(grouped.get_all(&2), &[2, 5, 8]);
+    assert_eq!(grouped.len(), 10);
+}
@@ -0,0 +1,514 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::{self, Debug};
+use core::iter::FusedIterator;
+use core::mem;
+use core::ops::{Bound, Range};
+
+use crate::map::Field;
+use crate::{Map, Sort};
+
+/// An ordered collection of non-overlapping ranges of `K`, each associated with
+/// a value.
+///
+/// Ranges are half-open: a range of `start..end` contains every key that is
+/// greater than or equal to `start` and less than `end`. Inserting a range
+/// that overlaps existing ranges trims or splits the existing ranges so that
+/// the inserted range takes precedence.
+///
+/// The ranges are stored in a [`Map`] keyed by the start of each range, which
+/// allows looking up the range containing a key using a single
+/// [floor search](Map::floor).
+///
+/// ```rust
+/// use kempt::RangeMap;
+///
+/// let mut map = RangeMap::new();
+/// map.insert(0..10, 'a');
+/// map.insert(3..5, 'b');
+/// assert_eq!(map.get(&2), Some(&'a'));
+/// assert_eq!(map.get(&3), Some(&'b'));
+/// assert_eq!(map.get(&5), Some(&'a'));
+/// assert_eq!(map.get(&10), None);
+/// assert!(map.iter().eq([(&0..&3, &'a'), (&3..&5, &'b'), (&5..&10, &'a')]));
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct RangeMap<K, V>
+where
+    K: Sort<K>,
+{
+    ranges: Map<K, (K, V)>,
+}
+
+impl<K, V> Default for RangeMap<K, V>
+where
+    K: Sort<K>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> RangeMap<K, V>
+where
+    K: Sort<K>,
+{
+    /// Returns an empty map.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { ranges: Map::new() }
+    }
+
+    /// Associates every key in `range` with `value`.
+    ///
+    /// Any existing ranges that overlap `range` are trimmed, split, or
+    /// removed. If `range` is empty, the map is not modified.
+    #[inline]
+    pub fn insert(&mut self, range: Range<K>, value: V)
+    where
+        K: Clone,
+        V: Clone,
+    {
+        self.replace(range, Some(value));
+    }
+
+    /// Removes every key in `range` from the map.
+    ///
+    /// Any existing ranges that overlap `range` are trimmed, split, or
+    /// removed.
+    ///
+    /// ```rust
+    /// use kempt::RangeMap;
+    ///
+    /// let mut map = RangeMap::from_iter([(0..10, 'a')]);
+    /// map.remove(3..5);
+    /// assert!(map.iter().eq([(&0..&3, &'a'), (&5..&10, &'a')]));
+    /// ```
+    #[inline]
+    pub fn remove(&mut self, range: Range<K>)
+    where
+        K: Clone,
+        V: Clone,
+    {
+        self.replace(range, None);
+    }
+
+    /// Returns the value associated with the range containing `key`, if found.
+    #[inline]
+    pub fn get<SearchFor>(&self, key: &SearchFor) -> Option<&V>
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.get_range_value(key).map(|(_, value)| value)
+    }
+
+    /// Returns the range containing `key` and its associated value, if found.
+    #[inline]
+    pub fn get_range_value<SearchFor>(&self, key: &SearchFor) -> Option<(Range<&K>, &V)>
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        let field = self.ranges.floor(key)?;
+        let (end, value) = &field.value;
+        (end.compare(key) == Ordering::Greater).then_some((field.key()..end, value))
+    }
+
+    /// Returns true if `key` is contained in any range in this map.
+    #[inline]
+    pub fn contains<SearchFor>(&self, key: &SearchFor) -> bool
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.get_range_value(key).is_some()
+    }
+
+    /// Returns the number of ranges in this map.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Returns true if there are no ranges in this map.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns an iterator over the ranges in this map and their associated
+    /// values.
+    ///
+    /// This iterator is guaranteed to return results in the sort order of the
+    /// `K` type.
+    #[must_use]
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.into_iter()
+    }
+
+    /// Returns an iterator over the ranges in this map that overlap `range`,
+    /// and their associated values.
+    ///
+    /// The returned ranges are not trimmed to fit within `range`.
+    ///
+    /// ```rust
+    /// use kempt::RangeMap;
+    ///
+    /// let map = RangeMap::from_iter([(0..3, 'a'), (3..5, 'b'), (7..10, 'c')]);
+    /// assert!(map
+    ///     .overlapping(&(4..8))
+    ///     .eq([(&3..&5, &'b'), (&7..&10, &'c')]));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn overlapping(&self, range: &Range<K>) -> Iter<'_, K, V> {
+        Iter(self.ranges.fields()[self.overlapping_indices(range)].iter())
+    }
+
+    /// Returns an iterator over the ranges within `range` that are not
+    /// contained in this map.
+    ///
+    /// ```rust
+    /// use kempt::RangeMap;
+    ///
+    /// let map = RangeMap::from_iter([(2..4, 'a'), (4..6, 'b'), (8..10, 'c')]);
+    /// assert!(map.gaps(&(0..12)).eq([&0..&2, &6..&8, &10..&12]));
+    /// assert!(map.gaps(&(3..9)).eq([&6..&8]));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn gaps<'a>(&'a self, range: &'a Range<K>) -> Gaps<'a, K, V> {
+        Gaps {
+            ranges: self.overlapping(range),
+            cursor: Some(&range.start),
+            end: &range.end,
+        }
+    }
+
+    /// Merges adjacent ranges that are associated with equal values.
+    ///
+    /// ```rust
+    /// use kempt::RangeMap;
+    ///
+    /// let mut map = RangeMap::from_iter([(0..2, 'a'), (2..4, 'a'), (5..6, 'a'), (6..8, 'b')]);
+    /// map.coalesce();
+    /// assert!(map.iter().eq([(&0..&4, &'a'), (&5..&6, &'a'), (&6..&8, &'b')]));
+    /// ```
+    pub fn coalesce(&mut self)
+    where
+        V: PartialEq,
+    {
+        let mut coalesced = Vec::<Field<K, (K, V)>>::with_capacity(self.ranges.len());
+        for field in mem::take(&mut self.ranges) {
+            let (start, (end, value)) = field.into_parts();
+            if let Some(previous) = coalesced.last_mut() {
+                let (previous_end, previous_value) = &mut previous.value;
+                if previous_end.compare(&start) == Ordering::Equal && *previous_value == value {
+                    *previous_end = end;
+                    continue;
+                }
+            }
+            coalesced.push(Field::new(start, (end, value)));
+        }
+        self.ranges = Map::from_sorted_fields(coalesced);
+    }
+
+    /// Clears the contents of this collection.
+    ///
+    /// This does not return any allocated memory to the OS.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.ranges.clear();
+    }
+
+    /// Returns the indices of the stored ranges that overlap `range`.
+    fn overlapping_indices(&self, range: &Range<K>) -> Range<usize> {
+        let start = match self.ranges.floor_index(&range.start) {
+            Some(index)
+                if self.ranges.fields()[index].value.0.compare(&range.start)
+                    == Ordering::Greater =>
+            {
+                index
+            }
+            Some(index) => index + 1,
+            None => 0,
+        };
+        let end = self
+            .ranges
+            .range_indices((Bound::Unbounded, Bound::Excluded(&range.end)))
+            .end;
+        start..end.max(start)
+    }
+
+    /// Replaces the contents of `range` with `value`, trimming or splitting
+    /// any overlapping ranges.
+    fn replace(&mut self, range: Range<K>, value: Option<V>)
+    where
+        K: Clone,
+        V: Clone,
+    {
+        if range.start.compare(&range.end) != Ordering::Less {
+            return;
+        }
+
+        let indices = self.overlapping_indices(&range);
+        let overlapping = &self.ranges.fields()[indices.clone()];
+        let mut replacement = Vec::with_capacity(3);
+        if let Some(first) = overlapping.first() {
+            if first.key().compare(&range.start) == Ordering::Less {
+                replacement.push(Field::new(
+                    first.key().clone(),
+                    (range.start.clone(), first.value.1.clone()),
+                ));
+            }
+        }
+        let trailing = overlapping.last().and_then(|last| {
+            let (end, value) = &last.value;
+            (end.compare(&range.end) == Ordering::Greater)
+                .then(|| Field::new(range.end.clone(), (end.clone(), value.clone())))
+        });
+        if let Some(value) = value {
+            replacement.push(Field::new(range.start, (range.end, value)));
+        }
+        replacement.extend(trailing);
+
+        self.ranges.splice_fields(indices, replacement);
+    }
+}
+
+impl<K, V> Debug for RangeMap<K, V>
+where
+    K: Sort<K> + Debug,
+    V: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_map();
+        for (range, value) in self {
+            s.entry(&range, value);
+        }
+        s.finish()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a RangeMap<K, V>
+where
+    K: Sort<K>,
+{
+    type IntoIter = Iter<'a, K, V>;
+    type Item = (Range<&'a K>, &'a V);
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        Iter(self.ranges.fields().iter())
+    }
+}
+
+impl<K, V> FromIterator<(Range<K>, V)> for RangeMap<K, V>
+where
+    K: Sort<K> + Clone,
+    V: Clone,
+{
+    /// Collects `iter` into a map, inserting each range in order. Ranges
+    /// returned later take precedence over earlier ranges they overlap.
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (Range<K>, V)>>(iter: T) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V> Extend<(Range<K>, V)> for RangeMap<K, V>
+where
+    K: Sort<K> + Clone,
+    V: Clone,
+{
+    #[inline]
+    fn extend<T: IntoIterator<Item = (Range<K>, V)>>(&mut self, iter: T) {
+        for (range, value) in iter {
+            self.insert(range, value);
+        }
+    }
+}
+
+/// An iterator over the ranges in a [`RangeMap`] and their associated values.
+///
+/// This iterator is guaranteed to return results in the sort order of the `K`
+/// type.
+pub struct Iter<'a, K, V>(core::slice::Iter<'a, Field<K, (K, V)>>);
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (Range<&'a K>, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(range_value)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Iter<'_, K, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(range_value)
+    }
+}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+#[inline]
+fn range_value<K, V>(field: &Field<K, (K, V)>) -> (Range<&K>, &V) {
+    let (end, value) = &field.value;
+    (field.key()..end, value)
+}
+
+/// An iterator over the ranges not contained in a [`RangeMap`] within a
+/// searched range.
+///
+/// This iterator is guaranteed to return results in the sort order of the `K`
+/// type.
+pub struct Gaps<'a, K, V> {
+    ranges: Iter<'a, K, V>,
+    cursor: Option<&'a K>,
+    end: &'a K,
+}
+
+impl<'a, K, V> Iterator for Gaps<'a, K, V>
+where
+    K: Sort<K>,
+{
+    type Item = Range<&'a K>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let cursor = self.cursor?;
+            let (gap, next_cursor) = match self.ranges.next() {
+                Some((range, _)) => (cursor..range.start, Some(range.end)),
+                None => (cursor..self.end, None),
+            };
+            self.cursor = next_cursor;
+            if gap.start.compare(gap.end) == Ordering::Less {
+                return Some(gap);
+            }
+        }
+    }
+}
+
+impl<K, V> FusedIterator for Gaps<'_, K, V> where K: Sort<K> {}
+
+#[test]
+fn insert_and_remove() {
+    let mut map = RangeMap::default();
+    assert!(map.is_empty());
+    map.insert(10..20, 'a');
+    map.insert(30..40, 'b');
+    // Empty and reversed ranges are ignored.
+    map.insert(5..5, 'z');
+    let (start, end) = (8, 6);
+    map.insert(start..end, 'z');
+    assert_eq!(map.len(), 2);
+
+    // Trim the end of one range and the start of another.
+    map.insert(15..35, 'c');
+    assert!(map
+        .iter()
+        .eq([(&10..&15, &'a'), (&15..&35, &'c'), (&35..&40, &'b')]));
+
+    // Split a single range.
+    map.insert(20..25, 'd');
+    assert!(map.iter().eq([
+        (&10..&15, &'a'),
+        (&15..&20, &'c'),
+        (&20..&25, &'d'),
+        (&25..&35, &'c'),
+        (&35..&40, &'b')
+    ]));
+
+    // Replace several ranges entirely.
+    map.insert(0..50, 'e');
+    assert!(map.iter().eq([(&0..&50, &'e')]));
+
+    map.remove(10..20);
+    map.remove(40..60);
+    map.remove(100..200);
+    assert!(map.iter().eq([(&0..&10, &'e'), (&20..&40, &'e')]));
+    map.remove(0..100);
+    assert!(map.is_empty());
+
+    map.insert(0..1, 'a');
+    map.clear();
+    assert!(map.is_empty());
+}
+
+#[test]
+fn lookup() {
+    use alloc::format;
+
+    let map = RangeMap::from_iter([(0..10, 'a'), (10..20, 'b'), (30..40, 'c')]);
+    assert_eq!(map.get(&-1), None);
+    assert_eq!(map.get(&0), Some(&'a'));
+    assert_eq!(map.get(&9), Some(&'a'));
+    assert_eq!(map.get(&10), Some(&'b'));
+    assert_eq!(map.get(&25), None);
+    assert_eq!(map.get(&39), Some(&'c'));
+    assert_eq!(map.get(&40), None);
+    assert_eq!(map.get_range_value(&35), Some((&30..&40, &'c')));
+    assert!(map.contains(&15));
+    assert!(!map.contains(&20));
+
+    assert!(map.overlapping(&(10..30)).eq([(&10..&20, &'b')]));
+    assert!(map
+        .overlapping(&(9..31))
+        .map(|(_, value)| *value)
+        .eq(['a', 'b', 'c']));
+    assert_eq!(map.overlapping(&(20..30)).len(), 0);
+    let (start, end) = (30, 20);
+    assert_eq!(map.overlapping(&(start..end)).len(), 0);
+    assert!(map
+        .overlapping(&(0..100))
+        .rev()
+        .map(|(_, value)| *value)
+        .eq(['c', 'b', 'a']));
+
+    assert!(map.gaps(&(-10..50)).eq([&-10..&0, &20..&30, &40..&50]));
+    assert!(map.gaps(&(0..40)).eq([&20..&30]));
+    assert_eq!(map.gaps(&(1..9)).next(), None);
+    assert!(RangeMap::<i32, ()>::new().gaps(&(1..9)).eq([&1..&9]));
+
+    assert_eq!(format!("{map:?}"), "{0..10: 'a', 10..20: 'b', 30..40: 'c'}");
+}
+
+#[test]
+fn coalesce() {
+    let mut map = RangeMap::from_iter([
+        (0..5, 1),
+        (5..10, 1),
+        (10..15, 1),
+        (15..20, 2),
+        (21..25, 2),
+        (25..30, 2),
+    ]);
+    map.coalesce();
+    assert!(map
+        .iter()
+        .eq([(&0..&15, &1), (&15..&20, &2), (&21..&30, &2)]));
+}
@@ -0,0 +1,746 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::{self, Debug};
+use core::iter::FusedIterator;
+use core::ops::{BitAnd, BitOr, Bound, RangeBounds, RangeInclusive};
+use core::slice;
+
+use crate::map::{Field, Unioned};
+use crate::{Map, Sort};
+
+/// A type with discrete values that can be stepped through in order.
+///
+/// This trait is used by [`RangeSet`] to determine whether two ranges are
+/// adjacent, and is implemented for all primitive integer types.
+pub trait Step: Sort<Self> + Clone + Sized {
+    /// The smallest value of this type.
+    const MIN: Self;
+    /// The largest value of this type.
+    const MAX: Self;
+
+    /// Returns the value immediately after `self`, or `None` if `self` is
+    /// [`Step::MAX`].
+    fn successor(&self) -> Option<Self>;
+
+    /// Returns the value immediately before `self`, or `None` if `self` is
+    /// [`Step::MIN`].
+    fn predecessor(&self) -> Option<Self>;
+
+    /// Returns the number of steps from `start` to `end`, saturating at
+    /// `usize::MAX`. `end` must be greater than or equal to `start`.
+    fn steps_between(start: &Self, end: &Self) -> usize;
+}
+
+macro_rules! impl_step {
+    ($($type:ty),+) => {
+        $(
+            impl Step for $type {
+                const MIN: Self = <$type>::MIN;
+                const MAX: Self = <$type>::MAX;
+
+                #[inline]
+                fn successor(&self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                #[inline]
+                fn predecessor(&self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                #[inline]
+                fn steps_between(start: &Self, end: &Self) -> usize {
+                    usize::try_from(end.abs_diff(*start)).unwrap_or(usize::MAX)
+                }
+            }
+        )+
+    };
+}
+
+impl_step!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// An ordered set of discrete values, stored as disjoint ranges.
+///
+/// Contiguous runs of members are coalesced into a single range, which allows
+/// sets such as allocated port numbers or received sequence numbers to be
+/// stored using far less memory than a [`Set`](crate::Set).
+///
+/// The ranges are stored in a [`Map`] keyed by the start of each range, and
+/// each range's inclusive end.
+///
+/// ```rust
+/// use kempt::RangeSet;
+///
+/// let mut set = RangeSet::new();
+/// set.insert_range(1..=3);
+/// set.insert(5);
+/// set.insert(4);
+/// assert_eq!(set.range_count(), 1);
+/// assert_eq!(set.len(), 5);
+/// assert!(set.contains(&3));
+/// assert!(!set.contains(&6));
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct RangeSet<T>
+where
+    T: Step,
+{
+    ranges: Map<T, T>,
+}
+
+impl<T> Default for RangeSet<T>
+where
+    T: Step,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> RangeSet<T>
+where
+    T: Step,
+{
+    /// Returns an empty set.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { ranges: Map::new() }
+    }
+
+    /// Inserts `value` into the set, returning true if it was not already a
+    /// member.
+    #[inline]
+    pub fn insert(&mut self, value: T) -> bool {
+        if self.contains(&value) {
+            false
+        } else {
+            self.insert_range(value.clone()..=value);
+            true
+        }
+    }
+
+    /// Inserts every value in `range` into the set.
+    ///
+    /// Any ranges that overlap or are adjacent to `range` are coalesced into a
+    /// single range.
+    pub fn insert_range(&mut self, range: impl RangeBounds<T>) {
+        let Some((mut start, mut end)) = bounds(&range) else {
+            return;
+        };
+
+        let fields = self.ranges.fields();
+        let first = match self.ranges.floor_index(&start) {
+            Some(index) if reaches(&fields[index].value, &start) => index,
+            Some(index) => index + 1,
+            None => 0,
+        };
+        let last = match end.successor() {
+            Some(after_end) => {
+                self.ranges
+                    .range_indices((Bound::Unbounded, Bound::Included(&after_end)))
+                    .end
+            }
+            None => fields.len(),
+        }
+        .max(first);
+
+        let merged = &fields[first..last];
+        if let Some(field) = merged.first() {
+            if field.key().compare(&start) == Ordering::Less {
+                start = field.key().clone();
+            }
+        }
+        if let Some(field) = merged.last() {
+            if field.value.compare(&end) == Ordering::Greater {
+                end = field.value.clone();
+            }
+        }
+
+        self.ranges
+            .splice_fields(first..last, [Field::new(start, end)]);
+    }
+
+    /// Removes `value` from the set, returning true if it was a member.
+    #[inline]
+    pub fn remove(&mut self, value: &T) -> bool {
+        if self.contains(value) {
+            self.remove_range(value.clone()..=value.clone());
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Removes every value in `range` from the set.
+    ///
+    /// Any ranges that overlap `range` are trimmed, split, or removed.
+    ///
+    /// ```rust
+    /// use kempt::RangeSet;
+    ///
+    /// let mut set = RangeSet::from_iter([0..=9]);
+    /// set.remove_range(3..5);
+    /// assert!(set.ranges().eq([&0..=&2, &5..=&9]));
+    /// ```
+    pub fn remove_range(&mut self, range: impl RangeBounds<T>) {
+        let Some((start, end)) = bounds(&range) else {
+            return;
+        };
+
+        let indices = self.overlapping_indices(&start, &end);
+        let overlapping = &self.ranges.fields()[indices.clone()];
+        let mut remaining = Vec::with_capacity(2);
+        if let Some(field) = overlapping.first() {
+            if let (Ordering::Less, Some(before_start)) =
+                (field.key().compare(&start), start.predecessor())
+            {
+                remaining.push(Field::new(field.key().clone(), before_start));
+            }
+        }
+        if let Some(field) = overlapping.last() {
+            if let (Ordering::Greater, Some(after_end)) =
+                (field.value.compare(&end), end.successor())
+            {
+                remaining.push(Field::new(after_end, field.value.clone()));
+            }
+        }
+
+        self.ranges.splice_fields(indices, remaining);
+    }
+
+    /// Returns true if `value` is a member of this set.
+    #[inline]
+    pub fn contains(&self, value: &T) -> bool {
+        self.ranges
+            .floor(value)
+            .map_or(false, |field| field.value.compare(value) != Ordering::Less)
+    }
+
+    /// Returns the number of members in this set, saturating at `usize::MAX`.
+    ///
+    /// This function visits each range stored in this set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ranges.iter().fold(0_usize, |len, field| {
+            len.saturating_add(T::steps_between(field.key(), &field.value))
+                .saturating_add(1)
+        })
+    }
+
+    /// Returns the number of disjoint ranges stored in this set.
+    #[must_use]
+    #[inline]
+    pub fn range_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Returns true if there are no members in this set.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns an iterator over the members of this set.
+    ///
+    /// This iterator is guaranteed to return results in the sort order of the
+    /// `T` type.
+    #[must_use]
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.into_iter()
+    }
+
+    /// Returns an iterator over the disjoint, inclusive ranges of members in
+    /// this set.
+    ///
+    /// This iterator is guaranteed to return results in the sort order of the
+    /// `T` type.
+    #[must_use]
+    #[inline]
+    pub fn ranges(&self) -> Ranges<'_, T> {
+        Ranges(self.ranges.fields().iter())
+    }
+
+    /// Returns an iterator over the inclusive ranges of values within `range`
+    /// that are not members of this set.
+    ///
+    /// ```rust
+    /// use kempt::RangeSet;
+    ///
+    /// let set = RangeSet::from_iter([2..=3, 6..=7]);
+    /// assert!(set.gaps(0..10).eq([0..=1, 4..=5, 8..=9]));
+    /// assert!(set.gaps(3..=6).eq([4..=5]));
+    /// ```
+    #[must_use]
+    pub fn gaps(&self, range: impl RangeBounds<T>) -> Gaps<'_, T> {
+        match bounds(&range) {
+            Some((start, end)) => Gaps {
+                ranges: self.ranges.fields()[self.overlapping_indices(&start, &end)].iter(),
+                cursor: Some(start),
+                end,
+            },
+            None => Gaps {
+                ranges: [].iter(),
+                cursor: None,
+                end: T::MIN,
+            },
+        }
+    }
+
+    /// Returns a new set containing the values within `range` that are not
+    /// members of this set.
+    #[must_use]
+    #[inline]
+    pub fn complement(&self, range: impl RangeBounds<T>) -> Self {
+        Self {
+            ranges: Map::from_sorted_fields(
+                self.gaps(range)
+                    .map(|gap| {
+                        let (start, end) = gap.into_inner();
+                        Field::new(start, end)
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Returns a new set containing the members of both `self` and `other`.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut ranges =
+            Vec::<Field<T, T>>::with_capacity(self.range_count() + other.range_count());
+        for unioned in self.ranges.union(&other.ranges) {
+            let (start, end) = match unioned {
+                Unioned::Left { key, value } | Unioned::Right { key, value } => (key, value),
+                Unioned::Both { key, left, right } => (key, max(left, right)),
+            };
+            if let Some(previous) = ranges.last_mut() {
+                // Ranges are visited in order of their start, so a range that
+                // reaches this range's start can be extended in place.
+                if reaches(&previous.value, start) {
+                    if end.compare(&previous.value) == Ordering::Greater {
+                        previous.value = end.clone();
+                    }
+                    continue;
+                }
+            }
+            ranges.push(Field::new(start.clone(), end.clone()));
+        }
+        Self {
+            ranges: Map::from_sorted_fields(ranges),
+        }
+    }
+
+    /// Returns a new set containing the values that are members of both
+    /// `self` and `other`.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut left = self.ranges.fields().iter().peekable();
+        let mut right = other.ranges.fields().iter().peekable();
+        while let (Some(a), Some(b)) = (left.peek(), right.peek()) {
+            let start = max(a.key(), b.key());
+            let end = min(&a.value, &b.value);
+            if start.compare(end) != Ordering::Greater {
+                ranges.push(Field::new(start.clone(), end.clone()));
+            }
+            if a.value.compare(&b.value) == Ordering::Less {
+                left.next();
+            } else {
+                right.next();
+            }
+        }
+        Self {
+            ranges: Map::from_sorted_fields(ranges),
+        }
+    }
+
+    /// Clears the contents of this collection.
+    ///
+    /// This does not return any allocated memory to the OS.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.ranges.clear();
+    }
+
+    /// Returns the indices of the stored ranges that contain any values from
+    /// `start` to `end`, inclusive.
+    fn overlapping_indices(&self, start: &T, end: &T) -> core::ops::Range<usize> {
+        let first = match self.ranges.floor_index(start) {
+            Some(index) if reaches(&self.ranges.fields()[index].value, start) => index,
+            Some(index) => index + 1,
+            None => 0,
+        };
+        let last = self
+            .ranges
+            .range_indices((Bound::Unbounded, Bound::Included(end)))
+            .end;
+        first..last.max(first)
+    }
+}
+
+/// Returns the inclusive start and end of `range`, or `None` if `range` is
+/// empty.
+fn bounds<T>(range: &impl RangeBounds<T>) -> Option<(T, T)>
+where
+    T: Step,
+{
+    let start = match range.start_bound() {
+        Bound::Included(start) => start.clone(),
+        Bound::Excluded(start) => start.successor()?,
+        Bound::Unbounded => T::MIN,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(end) => end.clone(),
+        Bound::Excluded(end) => end.predecessor()?,
+        Bound::Unbounded => T::MAX,
+    };
+    (start.compare(&end) != Ordering::Greater).then_some((start, end))
+}
+
+/// Returns true if a range ending at `end` contains or is adjacent to `value`.
+fn reaches<T>(end: &T, value: &T) -> bool
+where
+    T: Step,
+{
+    end.compare(value) != Ordering::Less
+        || end.successor().map_or(false, |after_end| {
+            after_end.compare(value) == Ordering::Equal
+        })
+}
+
+fn max<'a, T>(a: &'a T, b: &'a T) -> &'a T
+where
+    T: Sort<T>,
+{
+    if a.compare(b) == Ordering::Less {
+        b
+    } else {
+        a
+    }
+}
+
+fn min<'a, T>(a: &'a T, b: &'a T) -> &'a T
+where
+    T: Sort<T>,
+{
+    if a.compare(b) == Ordering::Greater {
+        b
+    } else {
+        a
+    }
+}
+
+impl<T> BitOr<&RangeSet<T>> for &RangeSet<T>
+where
+    T: Step,
+{
+    type Output = RangeSet<T>;
+
+    /// Returns the [union](RangeSet::union) of `self` and `rhs`.
+    #[inline]
+    fn bitor(self, rhs: &RangeSet<T>) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+impl<T> BitAnd<&RangeSet<T>> for &RangeSet<T>
+where
+    T: Step,
+{
+    type Output = RangeSet<T>;
+
+    /// Returns the [intersection](RangeSet::intersection) of `self` and `rhs`.
+    #[inline]
+    fn bitand(self, rhs: &RangeSet<T>) -> Self::Output {
+        self.intersection(rhs)
+    }
+}
+
+impl<T> Debug for RangeSet<T>
+where
+    T: Step + Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_set();
+        for range in self.ranges() {
+            s.entry(&range);
+        }
+        s.finish()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RangeSet<T>
+where
+    T: Step,
+{
+    type IntoIter = Iter<'a, T>;
+    type Item = T;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        Iter {
+            ranges: self.ranges.fields().iter(),
+            current: None,
+        }
+    }
+}
+
+impl<T> FromIterator<T> for RangeSet<T>
+where
+    T: Step,
+{
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T> Extend<T> for RangeSet<T>
+where
+    T: Step,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for member in iter {
+            self.insert(member);
+        }
+    }
+}
+
+impl<T> FromIterator<RangeInclusive<T>> for RangeSet<T>
+where
+    T: Step,
+{
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T> Extend<RangeInclusive<T>> for RangeSet<T>
+where
+    T: Step,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert_range(range);
+        }
+    }
+}
+
+/// An iterator over the members of a [`RangeSet`].
+///
+/// This iterator is guaranteed to return results in the sort order of the `T`
+/// type.
+pub struct Iter<'a, T> {
+    ranges: slice::Iter<'a, Field<T, T>>,
+    current: Option<(T, &'a T)>,
+}
+
+impl<T> Iterator for Iter<'_, T>
+where
+    T: Step,
+{
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((member, end)) = self.current.take() {
+            self.current = member
+                .successor()
+                .filter(|next| next.compare(end) != Ordering::Greater)
+                .map(|next| (next, end));
+            return Some(member);
+        }
+
+        let range = self.ranges.next()?;
+        if range.key().compare(&range.value) == Ordering::Less {
+            self.current = range.key().successor().map(|next| (next, &range.value));
+        }
+        Some(range.key().clone())
+    }
+}
+
+impl<T> FusedIterator for Iter<'_, T> where T: Step {}
+
+/// An iterator over the disjoint, inclusive ranges of members in a
+/// [`RangeSet`].
+///
+/// This iterator is guaranteed to return results in the sort order of the `T`
+/// type.
+pub struct Ranges<'a, T>(slice::Iter<'a, Field<T, T>>);
+
+impl<'a, T> Iterator for Ranges<'a, T> {
+    type Item = RangeInclusive<&'a T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|field| field.key()..=&field.value)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for Ranges<'_, T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<T> DoubleEndedIterator for Ranges<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|field| field.key()..=&field.value)
+    }
+}
+
+impl<T> FusedIterator for Ranges<'_, T> {}
+
+/// An iterator over the inclusive ranges of values that are not members of a
+/// [`RangeSet`] within a searched range.
+///
+/// This iterator is guaranteed to return results in the sort order of the `T`
+/// type.
+pub struct Gaps<'a, T> {
+    ranges: slice::Iter<'a, Field<T, T>>,
+    cursor: Option<T>,
+    end: T,
+}
+
+impl<T> Iterator for Gaps<'_, T>
+where
+    T: Step,
+{
+    type Item = RangeInclusive<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let cursor = self.cursor.take()?;
+            let Some(range) = self.ranges.next() else {
+                return Some(cursor..=self.end.clone());
+            };
+            self.cursor = range
+                .value
+                .successor()
+                .filter(|next| next.compare(&self.end) != Ordering::Greater);
+            if cursor.compare(range.key()) == Ordering::Less {
+                if let Some(before_range) = range.key().predecessor() {
+                    return Some(cursor..=before_range);
+                }
+            }
+        }
+    }
+}
+
+impl<T> FusedIterator for Gaps<'_, T> where T: Step {}
+
+#[test]
+fn insert_and_remove() {
+    let mut set = RangeSet::default();
+    assert!(set.is_empty());
+    assert!(set.insert(5_u8));
+    assert!(!set.insert(5));
+    assert!(set.insert(7));
+    assert_eq!(set.range_count(), 2);
+    // Filling the hole coalesces both ranges.
+    assert!(set.insert(6));
+    assert!(set.ranges().eq([&5..=&7]));
+
+    set.insert_range(10..12);
+    set.insert_range(0..=3);
+    assert!(set.ranges().eq([&0..=&3, &5..=&7, &10..=&11]));
+    // Adjacent on both sides.
+    set.insert_range(4..=9);
+    assert!(set.ranges().eq([&0..=&11]));
+    set.insert_range(250..);
+    assert!(set.ranges().eq([&0..=&11, &250..=&255]));
+    assert_eq!(set.len(), 18);
+    assert!(set.contains(&255));
+    assert!(!set.contains(&249));
+
+    assert!(set.remove(&255));
+    assert!(!set.remove(&255));
+    set.remove_range(2..=4);
+    set.remove_range(..1);
+    set.remove_range(11..251);
+    assert!(set.ranges().eq([&1..=&1, &5..=&10, &251..=&254]));
+    set.remove_range(..);
+    assert!(set.is_empty());
+
+    // Empty ranges are ignored.
+    set.insert_range(5..5);
+    assert!(set.is_empty());
+    set.insert_range(..);
+    assert_eq!(set.len(), 256);
+    assert_eq!(set.range_count(), 1);
+    set.clear();
+    assert!(set.is_empty());
+
+    let full = RangeSet::<usize>::from_iter([0..=usize::MAX]);
+    assert_eq!(full.len(), usize::MAX);
+}
+
+#[test]
+fn iteration() {
+    use alloc::format;
+
+    let set = RangeSet::from_iter([9, 1, 2, 3, 7, -1]);
+    assert!(set.ranges().eq([&-1..=&-1, &1..=&3, &7..=&7, &9..=&9]));
+    assert!(set.iter().eq([-1, 1, 2, 3, 7, 9]));
+    assert!(set
+        .ranges()
+        .rev()
+        .map(|range| **range.start())
+        .eq([9, 7, 1, -1]));
+    assert_eq!(format!("{set:?}"), "{-1..=-1, 1..=3, 7..=7, 9..=9}");
+    assert!(RangeSet::from_iter([u8::MAX]).iter().eq([u8::MAX]));
+
+    assert!(set
+        .gaps(-5..=10)
+        .eq([-5..=-2, 0..=0, 4..=6, 8..=8, 10..=10]));
+    assert!(set.gaps(1..=3).eq([]));
+    assert!(set.gaps(2..=8).eq([4..=6, 8..=8]));
+    assert_eq!(set.gaps(5..5).next(), None);
+    assert!(RangeSet::from_iter([0_u8..=10]).gaps(..).eq([11..=255]));
+    assert!(RangeSet::from_iter([250_u8..=255]).gaps(..).eq([0..=249]));
+}
+
+#[test]
+fn set_operations() {
+    let a = RangeSet::from_iter([0..=4, 10..=14, 20..=24]);
+    let b = RangeSet::from_iter([3..=11, 15..=16, 20..=21, 30..=30]);
+
+    let union = &a | &b;
+    assert!(union.ranges().eq([&0..=&16, &20..=&24, &30..=&30]));
+    assert_eq!(union, &b | &a);
+    // Ranges separated by a single value are not coalesced.
+    let gapped = &RangeSet::from_iter([0..=1]) | &RangeSet::from_iter([3..=4]);
+    assert!(gapped.ranges().eq([&0..=&1, &3..=&4]));
+    let adjacent = &RangeSet::from_iter([0..=1]) | &RangeSet::from_iter([2..=4]);
+    assert!(adjacent.ranges().eq([&0..=&4]));
+
+    let intersection = &a & &b;
+    assert!(intersection.ranges().eq([&3..=&4, &10..=&11, &20..=&21]));
+    assert_eq!(intersection, &b & &a);
+    assert!((&a & &RangeSet::new()).is_empty());
+
+    let complement = a.complement(0..30);
+    assert!(complement.ranges().eq([&5..=&9, &15..=&19, &25..=&29]));
+    assert!((&complement & &a).is_empty());
+    assert_eq!((&complement | &a), RangeSet::from_iter([0..=29]));
+}
@@ -1,9 +1,10 @@
 use core::marker::PhantomData;
 
-use serde::de::{MapAccess, Visitor};
+use serde::de::{Error, MapAccess, Unexpected, Visitor};
 use serde::ser::{SerializeMap, SerializeSeq};
 use serde::{Deserialize, Serialize};
 
+use crate::map::{Edit, MapPatch};
 use crate::{Map, Set, Sort};
 
 impl<Key, Value> Serialize for Map<Key, Value>
@@ -119,6 +120,69 @@ where
     }
 }
 
+impl<Key, Value> Serialize for MapPatch<Key, Value>
+where
+    Key: Serialize + Sort<Key>,
+    Value: Serialize,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.edits.serialize(serializer)
+    }
+}
+
+impl<'de, Key, Value> Deserialize<'de> for MapPatch<Key, Value>
+where
+    Key: Deserialize<'de> + Sort<Key>,
+    Value: Deserialize<'de>,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Map::deserialize(deserializer).map(|edits| MapPatch { edits })
+    }
+}
+
+/// Edits are serialized as an `(old, new)` tuple of optional values.
+impl<Value> Serialize for Edit<Value>
+where
+    Value: Serialize,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        (self.old_value(), self.new_value()).serialize(serializer)
+    }
+}
+
+impl<'de, Value> Deserialize<'de> for Edit<Value>
+where
+    Value: Deserialize<'de>,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        match <(Option<Value>, Option<Value>)>::deserialize(deserializer)? {
+            (None, Some(new)) => Ok(Edit::Insert(new)),
+            (Some(old), None) => Ok(Edit::Remove(old)),
+            (Some(old), Some(new)) => Ok(Edit::Replace { old, new }),
+            (None, None) => Err(D::Error::invalid_value(
+                Unexpected::Option,
+                &"an old value, a new value, or both",
+            )),
+        }
+    }
+}
+
 #[test]
 fn map_tests() {
     use serde_test::{assert_de_tokens_error, assert_tokens, Token};
@@ -159,3 +223,48 @@ fn set_tests() {
 
     assert_de_tokens_error::<Set<u8>>(&[Token::U8(1)], "invalid type: integer `1`, expected a Set");
 }
+
+#[test]
+fn patch_tests() {
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+    let old = [(1, 1), (2, 2)].into_iter().collect::<Map<u8, u16>>();
+    let new = [(2, 3), (3, 4)].into_iter().collect::<Map<u8, u16>>();
+    let patch = old.diff(&new).collect::<MapPatch<_, _>>();
+    assert_tokens(
+        &patch,
+        &[
+            Token::Map { len: Some(3) },
+            Token::U8(1),
+            Token::Tuple { len: 2 },
+            Token::Some,
+            Token::U16(1),
+            Token::None,
+            Token::TupleEnd,
+            Token::U8(2),
+            Token::Tuple { len: 2 },
+            Token::Some,
+            Token::U16(2),
+            Token::Some,
+            Token::U16(3),
+            Token::TupleEnd,
+            Token::U8(3),
+            Token::Tuple { len: 2 },
+            Token::None,
+            Token::Some,
+            Token::U16(4),
+            Token::TupleEnd,
+            Token::MapEnd,
+        ],
+    );
+
+    assert_de_tokens_error::<Edit<u16>>(
+        &[
+            Token::Tuple { len: 2 },
+            Token::None,
+            Token::None,
+            Token::TupleEnd,
+        ],
+        "invalid value: Option value, expected an old value, a new value, or both",
+    );
+}
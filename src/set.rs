@@ -1,4 +1,12 @@
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
 use core::fmt::{self, Debug};
+use core::iter::FusedIterator;
+use core::mem;
+use core::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, RangeBounds, Sub, SubAssign,
+};
 
 use crate::map::{self, Field, OwnedOrRef};
 use crate::{Map, Sort};
@@ -164,6 +172,28 @@ where
         self.into_iter()
     }
 
+    /// Returns an iterator over the members contained in `range`.
+    ///
+    /// If the start of `range` is greater than its end, the returned iterator
+    /// will be empty.
+    ///
+    /// ```rust
+    /// use kempt::Set;
+    ///
+    /// let set = Set::from([1, 2, 3, 4]);
+    /// assert!(set.range(2..).copied().eq([2, 3, 4]));
+    /// assert!(set.range(..=2).copied().eq([1, 2]));
+    /// ```
+    #[inline]
+    pub fn range<SearchFor, R>(&self, range: R) -> Iter<'_, T>
+    where
+        T: Sort<SearchFor>,
+        R: RangeBounds<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.0.range_keys(range)
+    }
+
     /// Returns an iterator that yields a single reference to all members found
     /// in either `self` or `other`.
     ///
@@ -197,6 +227,99 @@ where
         Difference(self.0.difference(&other.0))
     }
 
+    /// Returns true if every member of `self` is also contained in `other`.
+    ///
+    /// ```rust
+    /// use kempt::Set;
+    ///
+    /// let a: Set<u8> = [1, 2].into_iter().collect();
+    /// let b: Set<u8> = [1, 2, 3].into_iter().collect();
+    /// assert!(a.is_subset(&b));
+    /// assert!(!b.is_subset(&a));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn is_subset(&self, other: &Set<T>) -> bool {
+        self.0.keys_subset_of(&other.0)
+    }
+
+    /// Returns true if every member of `other` is also contained in `self`.
+    #[must_use]
+    #[inline]
+    pub fn is_superset(&self, other: &Set<T>) -> bool {
+        self.0.keys_superset_of(&other.0)
+    }
+
+    /// Returns true if `self` and `other` have no members in common.
+    #[must_use]
+    #[inline]
+    pub fn is_disjoint(&self, other: &Set<T>) -> bool {
+        self.0.keys_disjoint_from(&other.0)
+    }
+
+    /// Returns an iterator that yields a single reference to all members found
+    /// in either `self` or `other`, but not in both.
+    ///
+    /// This iterator is guaranteed to return results in the sort order of the
+    /// `Key` type.
+    #[must_use]
+    #[inline]
+    pub fn symmetric_difference<'a>(&'a self, other: &'a Set<T>) -> SymmetricDifference<'a, T> {
+        SymmetricDifference(self.0.symmetric_difference(&other.0))
+    }
+
+    /// Returns an iterator that yields a single reference to all members found
+    /// in any of `sets`.
+    ///
+    /// The sets are merged using a binary heap of each set's next member,
+    /// making this more efficient than repeatedly calling
+    /// [`union()`](Self::union) when combining many sets.
+    ///
+    /// This iterator is guaranteed to return results in the sort order of the
+    /// `Key` type.
+    ///
+    /// ```rust
+    /// use kempt::Set;
+    ///
+    /// let a = Set::from([1, 4]);
+    /// let b = Set::from([2, 4]);
+    /// let c = Set::from([3, 4]);
+    /// assert!(Set::union_all([&a, &b, &c]).copied().eq([1, 2, 3, 4]));
+    /// ```
+    #[inline]
+    pub fn union_all<'a>(sets: impl IntoIterator<Item = &'a Set<T>>) -> UnionAll<'a, T>
+    where
+        T: 'a,
+    {
+        UnionAll(map::merge_many(sets.into_iter().map(|set| &set.0)))
+    }
+
+    /// Returns an iterator that yields a single reference to all members found
+    /// in every one of `sets`.
+    ///
+    /// If no sets are provided, the iterator will not return any results.
+    ///
+    /// This iterator is guaranteed to return results in the sort order of the
+    /// `Key` type.
+    ///
+    /// ```rust
+    /// use kempt::Set;
+    ///
+    /// let a = Set::from([1, 2, 4]);
+    /// let b = Set::from([2, 4]);
+    /// let c = Set::from([3, 4]);
+    /// assert!(Set::intersection_all([&a, &b, &c]).copied().eq([4]));
+    /// ```
+    #[inline]
+    pub fn intersection_all<'a>(
+        sets: impl IntoIterator<Item = &'a Set<T>>,
+    ) -> IntersectionAll<'a, T>
+    where
+        T: 'a,
+    {
+        IntersectionAll(map::intersect_many(sets.into_iter().map(|set| &set.0)))
+    }
+
     /// Returns an iterator over the contents of this set. After the iterator is
     /// dropped, this set will be empty.
     #[inline]
@@ -237,6 +360,159 @@ where
     }
 }
 
+impl<T> Set<T>
+where
+    T: Sort<T>,
+{
+    /// Returns a set containing the members returned from `members`, which
+    /// must be returned in sort order with no duplicates.
+    fn from_sorted_members(members: impl Iterator<Item = T>) -> Self {
+        let (min, max) = members.size_hint();
+        let mut fields = Vec::with_capacity(max.unwrap_or(min));
+        fields.extend(members.map(|member| Field::new(member, ())));
+        Self(Map::from_sorted_fields(fields))
+    }
+
+    /// Merges `incoming`, which must return members in sort order with no
+    /// duplicates, into `self` in a single pass. Members contained in both are
+    /// only kept if `keep_shared` is true, in which case the member from `self`
+    /// is kept.
+    fn merge_sorted(&mut self, incoming: impl Iterator<Item = T>, keep_shared: bool) {
+        let mut existing = mem::take(&mut self.0).into_iter().peekable();
+        let mut incoming = incoming.peekable();
+        let mut merged = Vec::with_capacity(existing.len() + incoming.size_hint().0);
+        loop {
+            match (existing.peek(), incoming.peek()) {
+                (Some(field), Some(member)) => match field.key().compare(member) {
+                    Ordering::Less => merged.push(existing.next().expect("just peeked")),
+                    Ordering::Equal => {
+                        let field = existing.next().expect("just peeked");
+                        incoming.next();
+                        if keep_shared {
+                            merged.push(field);
+                        }
+                    }
+                    Ordering::Greater => {
+                        let member = incoming.next().expect("just peeked");
+                        merged.push(Field::new(member, ()));
+                    }
+                },
+                (Some(_), None) => {
+                    merged.extend(existing);
+                    break;
+                }
+                (None, _) => {
+                    merged.extend(incoming.map(|member| Field::new(member, ())));
+                    break;
+                }
+            }
+        }
+        self.0 = Map::from_sorted_fields(merged);
+    }
+}
+
+impl<T> BitOr<&Set<T>> for &Set<T>
+where
+    T: Sort<T> + Clone,
+{
+    type Output = Set<T>;
+
+    /// Returns a new set containing the members found in either `self` or
+    /// `rhs`.
+    #[inline]
+    fn bitor(self, rhs: &Set<T>) -> Self::Output {
+        Set::from_sorted_members(self.union(rhs).cloned())
+    }
+}
+
+impl<T> BitAnd<&Set<T>> for &Set<T>
+where
+    T: Sort<T> + Clone,
+{
+    type Output = Set<T>;
+
+    /// Returns a new set containing the members found in both `self` and
+    /// `rhs`.
+    #[inline]
+    fn bitand(self, rhs: &Set<T>) -> Self::Output {
+        Set::from_sorted_members(self.intersection(rhs).cloned())
+    }
+}
+
+impl<T> Sub<&Set<T>> for &Set<T>
+where
+    T: Sort<T> + Clone,
+{
+    type Output = Set<T>;
+
+    /// Returns a new set containing the members found in `self` but not in
+    /// `rhs`.
+    #[inline]
+    fn sub(self, rhs: &Set<T>) -> Self::Output {
+        Set::from_sorted_members(self.difference(rhs).cloned())
+    }
+}
+
+impl<T> BitXor<&Set<T>> for &Set<T>
+where
+    T: Sort<T> + Clone,
+{
+    type Output = Set<T>;
+
+    /// Returns a new set containing the members found in either `self` or
+    /// `rhs`, but not in both.
+    #[inline]
+    fn bitxor(self, rhs: &Set<T>) -> Self::Output {
+        Set::from_sorted_members(self.symmetric_difference(rhs).cloned())
+    }
+}
+
+impl<T> BitOrAssign<&Set<T>> for Set<T>
+where
+    T: Sort<T> + Clone,
+{
+    /// Inserts clones of all members of `rhs` that are not already contained
+    /// in `self`.
+    #[inline]
+    fn bitor_assign(&mut self, rhs: &Set<T>) {
+        self.merge_sorted(rhs.iter().cloned(), true);
+    }
+}
+
+impl<T> BitAndAssign<&Set<T>> for Set<T>
+where
+    T: Sort<T>,
+{
+    /// Removes all members of `self` that are not contained in `rhs`.
+    #[inline]
+    fn bitand_assign(&mut self, rhs: &Set<T>) {
+        self.0.retain_keys_found_in(&rhs.0, true);
+    }
+}
+
+impl<T> SubAssign<&Set<T>> for Set<T>
+where
+    T: Sort<T>,
+{
+    /// Removes all members of `self` that are contained in `rhs`.
+    #[inline]
+    fn sub_assign(&mut self, rhs: &Set<T>) {
+        self.0.retain_keys_found_in(&rhs.0, false);
+    }
+}
+
+impl<T> BitXorAssign<&Set<T>> for Set<T>
+where
+    T: Sort<T> + Clone,
+{
+    /// Removes all members of `self` that are contained in `rhs`, and inserts
+    /// clones of all members of `rhs` that were not contained in `self`.
+    #[inline]
+    fn bitxor_assign(&mut self, rhs: &Set<T>) {
+        self.merge_sorted(rhs.iter().cloned(), false);
+    }
+}
+
 impl<T> Debug for Set<T>
 where
     T: Sort<T> + Debug,
@@ -264,6 +540,19 @@ where
     }
 }
 
+impl<T> IntoIterator for Set<T>
+where
+    T: Sort<T>,
+{
+    type IntoIter = IntoIter<T>;
+    type Item = T;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_keys()
+    }
+}
+
 impl<T> FromIterator<T> for Set<T>
 where
     T: Sort<T>,
@@ -274,6 +563,96 @@ where
     }
 }
 
+impl<T> Extend<T> for Set<T>
+where
+    T: Sort<T>,
+{
+    /// Inserts each member of `iter` that is not already contained in this
+    /// set.
+    ///
+    /// The members are sorted before being merged into this set, which avoids
+    /// shifting the existing members for each inserted member.
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let incoming = iter.into_iter().collect::<Set<T>>();
+        self.0.merge_from(incoming.0, |_, (), ()| {});
+    }
+}
+
+impl<'a, T> Extend<&'a T> for Set<T>
+where
+    T: Sort<T> + Copy + 'a,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Set<T>
+where
+    T: Sort<T>,
+{
+    #[inline]
+    fn from(members: [T; N]) -> Self {
+        members.into_iter().collect()
+    }
+}
+
+impl<T> From<Vec<T>> for Set<T>
+where
+    T: Sort<T>,
+{
+    #[inline]
+    fn from(members: Vec<T>) -> Self {
+        members.into_iter().collect()
+    }
+}
+
+impl<T> From<BTreeSet<T>> for Set<T>
+where
+    T: Ord,
+{
+    /// Converts a `BTreeSet` into a `Set`.
+    ///
+    /// Because a `BTreeSet` iterates its members in order, no sorting is
+    /// performed.
+    #[inline]
+    fn from(set: BTreeSet<T>) -> Self {
+        Self::from_sorted_members(set.into_iter())
+    }
+}
+
+impl<T> From<Set<T>> for BTreeSet<T>
+where
+    T: Ord,
+{
+    #[inline]
+    fn from(set: Set<T>) -> Self {
+        set.into_iter().collect()
+    }
+}
+
+impl<T> PartialEq<BTreeSet<T>> for Set<T>
+where
+    T: Sort<T> + PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &BTreeSet<T>) -> bool {
+        self.len() == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T> PartialEq<Set<T>> for BTreeSet<T>
+where
+    T: Sort<T> + PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Set<T>) -> bool {
+        other == self
+    }
+}
+
 /// An iterator that yields a single reference to all members found in either
 /// [`Set`] being unioned.
 ///
@@ -302,6 +681,20 @@ where
     }
 }
 
+impl<T> DoubleEndedIterator for Union<'_, T>
+where
+    T: Sort<T>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0
+            .next_back()
+            .map(|unioned| unioned.map_both(|_, (), ()| OwnedOrRef::Owned(())).key)
+    }
+}
+
+impl<T> FusedIterator for Union<'_, T> where T: Sort<T> {}
+
 /// An iterator that yields a single reference to all members found in both
 /// [`Set`]s being intersected.
 ///
@@ -328,6 +721,18 @@ where
     }
 }
 
+impl<T> DoubleEndedIterator for Intersection<'_, T>
+where
+    T: Sort<T>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(k, (), ())| k)
+    }
+}
+
+impl<T> FusedIterator for Intersection<'_, T> where T: Sort<T> {}
+
 /// An iterator that yields a single reference to all members found in one
 /// [`Set`], but not another.
 ///
@@ -354,6 +759,116 @@ where
     }
 }
 
+impl<T> DoubleEndedIterator for Difference<'_, T>
+where
+    T: Sort<T>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(k, ())| k)
+    }
+}
+
+impl<T> FusedIterator for Difference<'_, T> where T: Sort<T> {}
+
+/// An iterator that yields a single reference to all members found in exactly
+/// one of two [`Set`]s.
+///
+/// This iterator is guaranteed to return results in the sort order of the `Key`
+/// type.
+pub struct SymmetricDifference<'a, T>(map::SymmetricDifference<'a, T, ()>)
+where
+    T: Sort<T>;
+
+impl<'a, T> Iterator for SymmetricDifference<'a, T>
+where
+    T: Sort<T>,
+{
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0
+            .next()
+            .map(|unioned| unioned.map_both(|_, (), ()| OwnedOrRef::Owned(())).key)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for SymmetricDifference<'_, T>
+where
+    T: Sort<T>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0
+            .next_back()
+            .map(|unioned| unioned.map_both(|_, (), ()| OwnedOrRef::Owned(())).key)
+    }
+}
+
+impl<T> FusedIterator for SymmetricDifference<'_, T> where T: Sort<T> {}
+
+/// An iterator that yields a single reference to all members found in any of
+/// many [`Set`]s.
+///
+/// This iterator is guaranteed to return results in the sort order of the `Key`
+/// type.
+pub struct UnionAll<'a, T>(map::MergeMany<'a, T, ()>)
+where
+    T: Sort<T>;
+
+impl<'a, T> Iterator for UnionAll<'a, T>
+where
+    T: Sort<T>,
+{
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.advance(|_, ()| {})
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T> FusedIterator for UnionAll<'_, T> where T: Sort<T> {}
+
+/// An iterator that yields a single reference to all members found in every
+/// one of many [`Set`]s.
+///
+/// This iterator is guaranteed to return results in the sort order of the `Key`
+/// type.
+pub struct IntersectionAll<'a, T>(map::IntersectMany<'a, T, ()>)
+where
+    T: Sort<T>;
+
+impl<'a, T> Iterator for IntersectionAll<'a, T>
+where
+    T: Sort<T>,
+{
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.advance(|()| {})
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T> FusedIterator for IntersectionAll<'_, T> where T: Sort<T> {}
+
 /// An iterator that drains the contents of a [`Set`].
 ///
 /// When this is dropped, the remaining contents are drained.
@@ -398,6 +913,30 @@ fn union() {
     assert_eq!(a.union(&b).copied().collect::<Vec<_>>(), [1, 2, 3, 5, 6]);
 }
 
+#[test]
+fn reversed() {
+    let a = [1, 3, 5].into_iter().collect::<Set<u8>>();
+    let b = [2, 3, 4, 6].into_iter().collect::<Set<u8>>();
+    assert!(a.union(&b).rev().copied().eq([6, 5, 4, 3, 2, 1]));
+    assert!(a.intersection(&b).rev().copied().eq([3]));
+    assert!(a.difference(&b).rev().copied().eq([5, 1]));
+    assert!(b.difference(&a).rev().copied().eq([6, 4, 2]));
+
+    let mut union = a.union(&b);
+    assert_eq!(union.size_hint(), (4, Some(7)));
+    assert_eq!(union.next(), Some(&1));
+    assert_eq!(union.next_back(), Some(&6));
+    assert_eq!(union.next_back(), Some(&5));
+    assert_eq!(union.next(), Some(&2));
+    assert_eq!(union.next(), Some(&3));
+    assert_eq!(union.next_back(), Some(&4));
+    assert_eq!(union.next(), None);
+    assert_eq!(union.next_back(), None);
+
+    assert_eq!(a.intersection(&b).size_hint(), (0, Some(3)));
+    assert_eq!(b.difference(&a).size_hint(), (1, Some(4)));
+}
+
 #[test]
 fn intersection() {
     use alloc::vec::Vec;
@@ -420,6 +959,159 @@ fn difference() {
     assert_eq!(a.difference(&b).copied().collect::<Vec<_>>(), [1, 3]);
 }
 
+#[test]
+fn symmetric_difference() {
+    use alloc::vec::Vec;
+    let a = [1, 3, 5].into_iter().collect::<Set<u8>>();
+    let b = [2, 3, 4].into_iter().collect::<Set<u8>>();
+    assert_eq!(
+        a.symmetric_difference(&b).copied().collect::<Vec<_>>(),
+        [1, 2, 4, 5]
+    );
+    assert_eq!(
+        a.symmetric_difference(&b)
+            .rev()
+            .copied()
+            .collect::<Vec<_>>(),
+        [5, 4, 2, 1]
+    );
+
+    let b = [1, 3, 5].into_iter().collect::<Set<u8>>();
+    assert_eq!(a.symmetric_difference(&b).next(), None);
+}
+
+#[test]
+fn many() {
+    let sets = (2..6)
+        .map(|step| (0..30).step_by(step).collect::<Set<u8>>())
+        .collect::<Vec<_>>();
+    assert!(Set::union_all(&sets)
+        .copied()
+        .eq((0..30).filter(|i| [2, 3, 4, 5].iter().any(|step| i % step == 0))));
+    assert!(Set::intersection_all(&sets).copied().eq([0]));
+    assert!(Set::intersection_all(&sets[..2])
+        .copied()
+        .eq([0, 6, 12, 18, 24]));
+    assert_eq!(Set::<u8>::union_all([]).next(), None);
+    assert_eq!(Set::<u8>::intersection_all([]).next(), None);
+}
+
+#[test]
+fn relations() {
+    let a = [1, 3, 5].into_iter().collect::<Set<u8>>();
+    let b = [1, 2, 3, 4, 5].into_iter().collect::<Set<u8>>();
+    let c = [2, 4].into_iter().collect::<Set<u8>>();
+    assert!(a.is_subset(&b));
+    assert!(a.is_subset(&a));
+    assert!(!b.is_subset(&a));
+    assert!(b.is_superset(&a));
+    assert!(!c.is_superset(&a));
+    assert!(a.is_disjoint(&c));
+    assert!(!b.is_disjoint(&c));
+    assert!(Set::new().is_subset(&a));
+    assert!(Set::new().is_disjoint(&a));
+}
+
+#[test]
+fn operators() {
+    let a = [1, 3, 5].into_iter().collect::<Set<u8>>();
+    let b = [2, 3, 4].into_iter().collect::<Set<u8>>();
+    let collect = |set: &Set<u8>| set.iter().copied().collect::<Vec<_>>();
+
+    let union = &a | &b;
+    assert_eq!(collect(&union), [1, 2, 3, 4, 5]);
+    assert!(union.capacity() >= union.len());
+    assert_eq!(collect(&(&a & &b)), [3]);
+    assert_eq!(collect(&(&a - &b)), [1, 5]);
+    assert_eq!(collect(&(&a ^ &b)), [1, 2, 4, 5]);
+
+    let mut assigned = a.clone();
+    assigned |= &b;
+    assert_eq!(assigned, union);
+    let mut assigned = a.clone();
+    assigned &= &b;
+    assert_eq!(collect(&assigned), [3]);
+    let mut assigned = a.clone();
+    assigned -= &b;
+    assert_eq!(collect(&assigned), [1, 5]);
+    let mut assigned = a.clone();
+    assigned ^= &b;
+    assert_eq!(collect(&assigned), [1, 2, 4, 5]);
+
+    let mut assigned = Set::new();
+    assigned ^= &a;
+    assert_eq!(assigned, a);
+    assigned ^= &a;
+    assert!(assigned.is_empty());
+}
+
+#[test]
+fn conversions() {
+    let mut set = Set::from([3, 1, 2, 1]);
+    assert_eq!(set.len(), 3);
+    assert_eq!(set, Set::from(alloc::vec![1, 2, 3]));
+
+    set.extend([5, 4, 3]);
+    set.extend(&[0, 6]);
+    assert_eq!(
+        set.iter().copied().collect::<Vec<_>>(),
+        [0, 1, 2, 3, 4, 5, 6]
+    );
+
+    let mut owned = Vec::new();
+    for member in set {
+        owned.push(member);
+    }
+    assert_eq!(owned, [0, 1, 2, 3, 4, 5, 6]);
+
+    let mut set = Set::new();
+    set.extend([2, 2, 1]);
+    assert_eq!(set.into_iter().rev().collect::<Vec<_>>(), [2, 1]);
+}
+
+#[test]
+fn btree_interop() {
+    let btree = [3, 1, 2].into_iter().collect::<BTreeSet<u8>>();
+    let set = Set::from(btree.clone());
+    assert_eq!(set, btree);
+    assert_eq!(btree, set);
+    assert_eq!(BTreeSet::from(set.clone()), btree);
+
+    let mut other = set;
+    other.insert(4);
+    assert_ne!(other, btree);
+    other.remove(&3);
+    assert_ne!(other, btree);
+}
+
+#[test]
+fn range() {
+    use alloc::string::String;
+    use core::ops::Bound;
+
+    let set = Set::from([1, 3, 5, 7]);
+    let collect = |range: Iter<'_, u8>| range.copied().collect::<Vec<_>>();
+    assert_eq!(collect(set.range(..)), [1, 3, 5, 7]);
+    assert_eq!(collect(set.range(3..7)), [3, 5]);
+    assert_eq!(collect(set.range(2..=7)), [3, 5, 7]);
+    assert_eq!(
+        collect(set.range((Bound::Excluded(3), Bound::Unbounded))),
+        [5, 7]
+    );
+    assert_eq!(collect(set.range(..4)), [1, 3]);
+    assert_eq!(collect(set.range(8..)), []);
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = set.range(5..3);
+    assert_eq!(collect(reversed), []);
+
+    let set = Set::from([String::from("a"), String::from("b")]);
+    assert_eq!(
+        set.range::<str, _>((Bound::Excluded("a"), Bound::Unbounded))
+            .count(),
+        1
+    );
+}
+
 #[test]
 fn lookup() {
     let mut set = Set::with_capacity(1);
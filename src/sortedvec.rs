@@ -0,0 +1,507 @@
+use alloc::vec::{self, Vec};
+use core::cmp::Ordering;
+use core::fmt::{self, Debug};
+use core::mem;
+use core::ops::{Bound, RangeBounds};
+use core::slice;
+
+use crate::map::scan_limit;
+use crate::Sort;
+
+/// An iterator over the values in a [`SortedVec`].
+pub type Iter<'a, T> = slice::Iter<'a, T>;
+/// An iterator that converts a [`SortedVec`] into its owned values.
+pub type IntoIter<T> = vec::IntoIter<T>;
+
+/// A sorted collection of `T`s that allows duplicate values.
+///
+/// Unlike [`Set`](crate::Set), this collection can store multiple values that
+/// compare as equal. Equal values are kept in the order they were inserted.
+///
+/// Values are located using the same hybrid binary search and sequential scan
+/// algorithm as [`Map`](crate::Map).
+///
+/// ```rust
+/// use kempt::SortedVec;
+///
+/// let mut timestamps = SortedVec::new();
+/// timestamps.insert(3);
+/// timestamps.insert(1);
+/// timestamps.insert(3);
+/// assert_eq!(timestamps.as_slice(), &[1, 3, 3]);
+/// assert_eq!(timestamps.count(&3), 2);
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct SortedVec<T>(Vec<T>)
+where
+    T: Sort<T>;
+
+impl<T> Default for SortedVec<T>
+where
+    T: Sort<T>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SortedVec<T>
+where
+    T: Sort<T>,
+{
+    const SCAN_LIMIT: usize = scan_limit::<T, ()>();
+
+    /// Returns an empty collection.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Returns an empty collection with enough allocated memory to store
+    /// `capacity` values without reallocating.
+    #[must_use]
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity))
+    }
+
+    /// Returns the current capacity this collection can hold before it must
+    /// reallocate.
+    #[must_use]
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Inserts `value` after any values that are equal to it, returning the
+    /// index it was inserted at.
+    ///
+    /// ```rust
+    /// use kempt::SortedVec;
+    ///
+    /// let mut values = SortedVec::new();
+    /// values.insert((1, "first"));
+    /// values.insert((0, "zero"));
+    /// assert_eq!(values.insert((1, "second")), 2);
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, value: T) -> usize {
+        let index = self.partition_point(|existing| existing.compare(&value) != Ordering::Greater);
+        self.0.insert(index, value);
+        index
+    }
+
+    /// Removes the first value that is equal to `value`, returning it if
+    /// found.
+    #[inline]
+    pub fn remove_one<SearchFor>(&mut self, value: &SearchFor) -> Option<T>
+    where
+        T: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        let index = self.lower_bound(value);
+        let found = self.0.get(index)?.compare(value) == Ordering::Equal;
+        found.then(|| self.0.remove(index))
+    }
+
+    /// Removes the value at `index`.
+    ///
+    /// # Panics
+    ///
+    /// A panic will occur if `index` is greater than or equal to the
+    /// collection's length.
+    #[inline]
+    pub fn remove_by_index(&mut self, index: usize) -> T {
+        self.0.remove(index)
+    }
+
+    /// Returns true if this collection contains a value equal to `value`.
+    #[inline]
+    pub fn contains<SearchFor>(&self, value: &SearchFor) -> bool
+    where
+        T: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.0
+            .get(self.lower_bound(value))
+            .map_or(false, |found| found.compare(value) == Ordering::Equal)
+    }
+
+    /// Returns the number of values that are equal to `value`.
+    #[inline]
+    pub fn count<SearchFor>(&self, value: &SearchFor) -> usize
+    where
+        T: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.equal_range(value).len()
+    }
+
+    /// Returns the values that are equal to `value`, in the order they were
+    /// inserted.
+    ///
+    /// ```rust
+    /// use kempt::SortedVec;
+    ///
+    /// let values = SortedVec::from_iter([3, 1, 2, 1]);
+    /// assert_eq!(values.equal_range(&1), &[1, 1]);
+    /// assert!(values.equal_range(&4).is_empty());
+    /// ```
+    #[inline]
+    pub fn equal_range<SearchFor>(&self, value: &SearchFor) -> &[T]
+    where
+        T: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        &self.0[self.lower_bound(value)..self.upper_bound(value)]
+    }
+
+    /// Returns the values contained in `range`.
+    ///
+    /// If the start of `range` is greater than its end, the returned slice
+    /// will be empty.
+    ///
+    /// ```rust
+    /// use kempt::SortedVec;
+    ///
+    /// let values = SortedVec::from_iter([1, 2, 2, 3, 4]);
+    /// assert_eq!(values.range(2..4), &[2, 2, 3]);
+    /// assert_eq!(values.range(..=2), &[1, 2, 2]);
+    /// ```
+    #[inline]
+    pub fn range<SearchFor, R>(&self, range: R) -> &[T]
+    where
+        T: Sort<SearchFor>,
+        R: RangeBounds<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(start) => self.lower_bound(start),
+            Bound::Excluded(start) => self.upper_bound(start),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => self.upper_bound(end),
+            Bound::Excluded(end) => self.lower_bound(end),
+            Bound::Unbounded => self.0.len(),
+        };
+        &self.0[start..end.max(start)]
+    }
+
+    /// Merges the values from `other` into `self` in a single pass.
+    ///
+    /// Values from `other` are placed after any equal values from `self`.
+    ///
+    /// ```rust
+    /// use kempt::SortedVec;
+    ///
+    /// let mut a = SortedVec::from_iter([(1, 'a'), (3, 'a')]);
+    /// let b = SortedVec::from_iter([(1, 'b'), (2, 'b')]);
+    /// a.merge(b);
+    /// assert!(a.iter().map(|(_, source)| *source).eq(['a', 'b', 'b', 'a']));
+    /// ```
+    pub fn merge(&mut self, other: Self) {
+        let mut existing = mem::take(&mut self.0).into_iter().peekable();
+        let mut merged = Vec::with_capacity(existing.len() + other.len());
+        for value in other.0 {
+            while let Some(lesser) =
+                existing.next_if(|existing| existing.compare(&value) != Ordering::Greater)
+            {
+                merged.push(lesser);
+            }
+            merged.push(value);
+        }
+        merged.extend(existing);
+        self.0 = merged;
+    }
+
+    /// Returns the value at `index`, or `None` if `index` is greater than or
+    /// equal to the collection's length.
+    #[must_use]
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.0.get(index)
+    }
+
+    /// Returns the smallest value in this collection.
+    #[must_use]
+    #[inline]
+    pub fn first(&self) -> Option<&T> {
+        self.0.first()
+    }
+
+    /// Returns the largest value in this collection.
+    #[must_use]
+    #[inline]
+    pub fn last(&self) -> Option<&T> {
+        self.0.last()
+    }
+
+    /// Returns the number of values in this collection.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if this collection contains no values.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the values in this collection as a slice.
+    #[must_use]
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    /// Returns the values in this collection as a `Vec`.
+    #[must_use]
+    #[inline]
+    pub fn into_vec(self) -> Vec<T> {
+        self.0
+    }
+
+    /// Returns an iterator over the values in this collection.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.into_iter()
+    }
+
+    /// Clears the contents of this collection.
+    ///
+    /// This does not return any allocated memory to the OS.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Returns the index of the first value that is not less than `value`.
+    fn lower_bound<SearchFor>(&self, value: &SearchFor) -> usize
+    where
+        T: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.partition_point(|existing| existing.compare(value) == Ordering::Less)
+    }
+
+    /// Returns the index of the first value that is greater than `value`.
+    fn upper_bound<SearchFor>(&self, value: &SearchFor) -> usize
+    where
+        T: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.partition_point(|existing| existing.compare(value) != Ordering::Greater)
+    }
+
+    /// Returns the number of leading values that match `predicate`, which must
+    /// match a prefix of this collection.
+    fn partition_point(&self, mut predicate: impl FnMut(&T) -> bool) -> usize {
+        // Like `Map`, narrow the search window using a binary search until the
+        // window is small enough to scan sequentially.
+        let mut min = 0;
+        let mut max = self.0.len();
+        while max - min > Self::SCAN_LIMIT {
+            let midpoint = min + (max - min) / 2;
+            if predicate(&self.0[midpoint]) {
+                min = midpoint + 1;
+            } else {
+                max = midpoint;
+            }
+        }
+
+        self.0[min..max]
+            .iter()
+            .position(|value| !predicate(value))
+            .map_or(max, |relative_index| min + relative_index)
+    }
+}
+
+impl<T> Debug for SortedVec<T>
+where
+    T: Sort<T> + Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(&self.0).finish()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SortedVec<T>
+where
+    T: Sort<T>,
+{
+    type IntoIter = Iter<'a, T>;
+    type Item = &'a T;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<T> IntoIterator for SortedVec<T>
+where
+    T: Sort<T>,
+{
+    type IntoIter = IntoIter<T>;
+    type Item = T;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<T> FromIterator<T> for SortedVec<T>
+where
+    T: Sort<T>,
+{
+    /// Collects `iter` into a sorted collection. Equal values are kept in the
+    /// order they were returned from `iter`.
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<T> Extend<T> for SortedVec<T>
+where
+    T: Sort<T>,
+{
+    /// Extends this collection with the values from `iter`, sorting the new
+    /// values and merging them in a single pass.
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.merge(iter.into_iter().collect());
+    }
+}
+
+impl<T> From<Vec<T>> for SortedVec<T>
+where
+    T: Sort<T>,
+{
+    /// Sorts `values` into a new collection. Equal values are kept in their
+    /// original order.
+    #[inline]
+    fn from(mut values: Vec<T>) -> Self {
+        values.sort_by(T::compare);
+        Self(values)
+    }
+}
+
+#[test]
+fn basics() {
+    let mut values = SortedVec::default();
+    assert!(values.is_empty());
+    assert_eq!(values.insert((2, 'a')), 0);
+    assert_eq!(values.insert((1, 'a')), 0);
+    assert_eq!(values.insert((2, 'b')), 2);
+    assert_eq!(values.insert((2, 'c')), 3);
+    assert_eq!(values.len(), 4);
+    assert_eq!(values.first(), Some(&(1, 'a')));
+    assert_eq!(values.last(), Some(&(2, 'c')));
+    assert_eq!(values.get(1), Some(&(2, 'a')));
+
+    assert_eq!(values.remove_by_index(0), (1, 'a'));
+    assert_eq!(values.remove_one(&(2, 'b')), Some((2, 'b')));
+    assert_eq!(values.remove_one(&(2, 'b')), None);
+    assert!(values.iter().eq(&[(2, 'a'), (2, 'c')]));
+    values.clear();
+    assert!(values.is_empty());
+}
+
+#[test]
+fn duplicates() {
+    use alloc::format;
+
+    // Enough values to require narrowing with a binary search.
+    let mut values = (0..100).map(|value| value / 10).collect::<SortedVec<u8>>();
+    assert_eq!(values.count(&3), 10);
+    assert_eq!(values.count(&10), 0);
+    assert!(values.contains(&9));
+    assert!(!values.contains(&10));
+    assert_eq!(values.equal_range(&4).len(), 10);
+    assert_eq!(values.range(3..5).len(), 20);
+    assert_eq!(values.range(8..).len(), 20);
+    assert_eq!(
+        values.range((Bound::Excluded(8), Bound::Unbounded)).len(),
+        10
+    );
+    let (start, end) = (5, 2);
+    assert!(values.range(start..end).is_empty());
+
+    assert_eq!(values.remove_one(&3), Some(3));
+    assert_eq!(values.count(&3), 9);
+    assert_eq!(values.insert(3), 39);
+
+    values.extend([0, 11, 3]);
+    assert_eq!(values.len(), 103);
+    assert_eq!(values.count(&3), 11);
+    assert_eq!(values.last(), Some(&11));
+    assert_eq!(
+        format!("{:?}", SortedVec::from(alloc::vec![2, 1, 2])),
+        "[1, 2, 2]"
+    );
+}
+
+#[test]
+fn merge() {
+    /// An event that is ordered only by its timestamp.
+    #[derive(Debug, Clone, Copy)]
+    struct Event(u8, char);
+
+    impl PartialEq for Event {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl Eq for Event {}
+
+    impl PartialOrd for Event {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Event {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    let mut a = SortedVec::from_iter([Event(2, 'a'), Event(0, 'a'), Event(2, 'b'), Event(5, 'a')]);
+    a.insert(Event(2, 'c'));
+    let b = SortedVec::from_iter([Event(6, 'd'), Event(2, 'd'), Event(3, 'd')]);
+    // Equal values from `a` are kept before equal values from `b`.
+    a.merge(b);
+    assert!(a.iter().map(|event| (event.0, event.1)).eq([
+        (0, 'a'),
+        (2, 'a'),
+        (2, 'b'),
+        (2, 'c'),
+        (2, 'd'),
+        (3, 'd'),
+        (5, 'a'),
+        (6, 'd')
+    ]));
+    assert_eq!(a.remove_one(&Event(2, 'z')).map(|event| event.1), Some('a'));
+    assert!(a
+        .equal_range(&Event(2, 'z'))
+        .iter()
+        .map(|event| event.1)
+        .eq(['b', 'c', 'd']));
+
+    a.merge(SortedVec::new());
+    assert_eq!(a.len(), 7);
+    let mut empty = SortedVec::new();
+    empty.merge(a.clone());
+    assert_eq!(empty, a);
+    assert_eq!(a.into_vec().len(), 7);
+}
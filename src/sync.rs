@@ -0,0 +1,327 @@
+use alloc::sync::Arc;
+use core::fmt::{self, Debug};
+use core::ops::Deref;
+
+use crate::map::{self, Field};
+use crate::{set, Map, Set, Sort};
+
+/// A [`Map`] whose storage is shared between clones until it is modified.
+///
+/// Cloning this type only increments a reference count. The first call to
+/// [`make_mut()`](Self::make_mut) on a clone whose storage is shared copies the
+/// fields into new storage. The full read API of [`Map`] is available through
+/// [`Deref`].
+///
+/// ```rust
+/// use kempt::sync::CowMap;
+///
+/// let mut current = CowMap::from_iter([("a", 1)]);
+/// let snapshot = current.clone();
+/// assert!(current.ptr_eq(&snapshot));
+///
+/// current.make_mut().insert("b", 2);
+/// assert!(!current.ptr_eq(&snapshot));
+/// assert_eq!(current.get(&"b"), Some(&2));
+/// assert_eq!(snapshot.get(&"b"), None);
+/// ```
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct CowMap<K, V>(Arc<Map<K, V>>)
+where
+    K: Sort<K>;
+
+impl<K, V> Default for CowMap<K, V>
+where
+    K: Sort<K>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> CowMap<K, V>
+where
+    K: Sort<K>,
+{
+    /// Returns an empty map.
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self(Arc::new(Map::new()))
+    }
+
+    /// Returns a mutable reference to the underlying [`Map`], copying the
+    /// fields into new storage if they are shared with another clone.
+    #[inline]
+    pub fn make_mut(&mut self) -> &mut Map<K, V>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        Arc::make_mut(&mut self.0)
+    }
+
+    /// Returns true if `self` and `other` share the same storage.
+    ///
+    /// Two maps that share storage are guaranteed to contain the same fields.
+    /// Because this check does not compare any fields, it can be used to
+    /// cheaply detect whether a map has been modified since it was cloned.
+    #[must_use]
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// Returns the underlying [`Map`], copying the fields if the storage is
+    /// shared with another clone.
+    #[must_use]
+    #[inline]
+    pub fn into_map(self) -> Map<K, V>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        Arc::try_unwrap(self.0).unwrap_or_else(|shared| Map::clone(&shared))
+    }
+}
+
+impl<K, V> Clone for CowMap<K, V>
+where
+    K: Sort<K>,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<K, V> Deref for CowMap<K, V>
+where
+    K: Sort<K>,
+{
+    type Target = Map<K, V>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<K, V> Debug for CowMap<K, V>
+where
+    K: Sort<K> + Debug,
+    V: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&*self.0, f)
+    }
+}
+
+impl<K, V> From<Map<K, V>> for CowMap<K, V>
+where
+    K: Sort<K>,
+{
+    #[inline]
+    fn from(map: Map<K, V>) -> Self {
+        Self(Arc::new(map))
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a CowMap<K, V>
+where
+    K: Sort<K>,
+{
+    type IntoIter = map::Iter<'a, K, V>;
+    type Item = &'a Field<K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for CowMap<K, V>
+where
+    K: Sort<K>,
+{
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self::from(Map::from_iter(iter))
+    }
+}
+
+/// A [`Set`] whose storage is shared between clones until it is modified.
+///
+/// Cloning this type only increments a reference count. The first call to
+/// [`make_mut()`](Self::make_mut) on a clone whose storage is shared copies the
+/// members into new storage. The full read API of [`Set`] is available through
+/// [`Deref`].
+///
+/// ```rust
+/// use kempt::sync::CowSet;
+///
+/// let mut current = CowSet::from_iter([1, 2]);
+/// let snapshot = current.clone();
+/// current.make_mut().remove(&1);
+/// assert!(!current.contains(&1));
+/// assert!(snapshot.contains(&1));
+/// ```
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct CowSet<T>(Arc<Set<T>>)
+where
+    T: Sort<T>;
+
+impl<T> Default for CowSet<T>
+where
+    T: Sort<T>,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> CowSet<T>
+where
+    T: Sort<T>,
+{
+    /// Returns an empty set.
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        Self(Arc::new(Set::new()))
+    }
+
+    /// Returns a mutable reference to the underlying [`Set`], copying the
+    /// members into new storage if they are shared with another clone.
+    #[inline]
+    pub fn make_mut(&mut self) -> &mut Set<T>
+    where
+        T: Clone,
+    {
+        Arc::make_mut(&mut self.0)
+    }
+
+    /// Returns true if `self` and `other` share the same storage.
+    ///
+    /// Two sets that share storage are guaranteed to contain the same members.
+    /// Because this check does not compare any members, it can be used to
+    /// cheaply detect whether a set has been modified since it was cloned.
+    #[must_use]
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// Returns the underlying [`Set`], copying the members if the storage is
+    /// shared with another clone.
+    #[must_use]
+    #[inline]
+    pub fn into_set(self) -> Set<T>
+    where
+        T: Clone,
+    {
+        Arc::try_unwrap(self.0).unwrap_or_else(|shared| Set::clone(&shared))
+    }
+}
+
+impl<T> Clone for CowSet<T>
+where
+    T: Sort<T>,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T> Deref for CowSet<T>
+where
+    T: Sort<T>,
+{
+    type Target = Set<T>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> Debug for CowSet<T>
+where
+    T: Sort<T> + Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&*self.0, f)
+    }
+}
+
+impl<T> From<Set<T>> for CowSet<T>
+where
+    T: Sort<T>,
+{
+    #[inline]
+    fn from(set: Set<T>) -> Self {
+        Self(Arc::new(set))
+    }
+}
+
+impl<'a, T> IntoIterator for &'a CowSet<T>
+where
+    T: Sort<T>,
+{
+    type IntoIter = set::Iter<'a, T>;
+    type Item = &'a T;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<T> FromIterator<T> for CowSet<T>
+where
+    T: Sort<T>,
+{
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(Set::from_iter(iter))
+    }
+}
+
+#[test]
+fn cow_map() {
+    use alloc::string::String;
+
+    let mut map = CowMap::<String, u32>::new();
+    map.make_mut().insert(String::from("a"), 1);
+    let snapshot = map.clone();
+    assert!(map.ptr_eq(&snapshot));
+    assert_eq!(map, snapshot);
+    assert_eq!(map.get("a"), Some(&1));
+
+    *map.make_mut().get_mut("a").unwrap() += 1;
+    assert!(!map.ptr_eq(&snapshot));
+    assert_ne!(map, snapshot);
+    map.make_mut().insert(String::from("b"), 3);
+
+    assert!(map.iter().map(|field| field.value).eq([2, 3]));
+    assert_eq!(snapshot.len(), 1);
+    assert_eq!(snapshot.clone().into_map().get("a"), Some(&1));
+    assert_eq!(map.into_map().len(), 2);
+}
+
+#[test]
+fn cow_set() {
+    let mut set = CowSet::from_iter([3, 1, 2]);
+    let snapshot = set.clone();
+    assert!(set.ptr_eq(&snapshot));
+    assert!(set.make_mut().insert(4));
+    assert!(!set.ptr_eq(&snapshot));
+    assert!(set.iter().copied().eq([1, 2, 3, 4]));
+    assert!((&snapshot).into_iter().copied().eq([1, 2, 3]));
+    assert_eq!(snapshot.into_set().len(), 3);
+    assert_eq!(CowSet::<u8>::default().len(), 0);
+}
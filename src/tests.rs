@@ -1,4 +1,5 @@
 use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
 use alloc::rc::Rc;
 use alloc::string::String;
 use alloc::vec;
@@ -6,8 +7,10 @@ use alloc::vec::Vec;
 use core::borrow::Borrow;
 use std::println;
 
-use crate::map::{Entry, Field, Map};
-use crate::Set;
+use crate::map::{
+    Change, Conflict, Edit, EitherOrBoth, Entry, Field, Map, MapPatch, Resolution, Unioned,
+};
+use crate::{map, Set};
 
 #[test]
 fn basics() {
@@ -350,3 +353,595 @@ fn unioned_map_both_ref() {
     assert_eq!(merged.get(&"d"), Some(&4));
     assert_eq!(merged.len(), 4);
 }
+
+#[test]
+fn symmetric_difference() {
+    let a = [("a", 1), ("b", 2), ("c", 3)]
+        .into_iter()
+        .collect::<Map<_, _>>();
+    let b = [("b", 2), ("d", 4), ("e", 5)]
+        .into_iter()
+        .collect::<Map<_, _>>();
+    let forward = a
+        .symmetric_difference(&b)
+        .map(|unioned| match unioned {
+            Unioned::Left { key, value } => (*key, *value, true),
+            Unioned::Right { key, value } => (*key, *value, false),
+            Unioned::Both { .. } => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        forward,
+        [
+            ("a", 1, true),
+            ("c", 3, true),
+            ("d", 4, false),
+            ("e", 5, false)
+        ]
+    );
+
+    let mut iter = a.symmetric_difference(&b);
+    assert_eq!(iter.size_hint(), (0, Some(6)));
+    assert!(matches!(
+        iter.next_back(),
+        Some(Unioned::Right { key: &"e", .. })
+    ));
+    assert!(matches!(iter.next(), Some(Unioned::Left { key: &"a", .. })));
+    assert!(matches!(
+        iter.next_back(),
+        Some(Unioned::Right { key: &"d", .. })
+    ));
+    assert!(matches!(
+        iter.next_back(),
+        Some(Unioned::Left { key: &"c", .. })
+    ));
+    assert!(iter.next().is_none());
+    assert!(iter.next_back().is_none());
+}
+
+#[test]
+fn key_relations() {
+    // Similar sizes use the ordered walk.
+    let evens = (0..100).step_by(2).map(|i| (i, ())).collect::<Map<_, _>>();
+    let odds = (1..100).step_by(2).map(|i| (i, ())).collect::<Map<_, _>>();
+    let all = (0..100).map(|i| (i, i)).collect::<Map<_, _>>();
+    assert!(evens.keys_subset_of(&all));
+    assert!(!all.keys_subset_of(&evens));
+    assert!(all.keys_superset_of(&odds));
+    assert!(evens.keys_disjoint_from(&odds));
+    assert!(!evens.keys_disjoint_from(&all));
+    let mut with_extra = evens.clone();
+    with_extra.insert(101, ());
+    assert!(!with_extra.keys_subset_of(&all));
+
+    // Very different sizes search the larger map for each key.
+    let few = [(10, ()), (20, ())].into_iter().collect::<Map<_, _>>();
+    let not_found = [(10, ()), (21, ())].into_iter().collect::<Map<_, _>>();
+    let many = (0..10_000).map(|i| (i * 2, ())).collect::<Map<_, _>>();
+    assert!(few.keys_subset_of(&many));
+    assert!(!not_found.keys_subset_of(&many));
+    assert!(many.keys_superset_of(&few));
+    assert!(!many.keys_disjoint_from(&few));
+    assert!(!few.keys_disjoint_from(&many));
+    let far = [(1, ()), (3, ())].into_iter().collect::<Map<_, _>>();
+    assert!(far.keys_disjoint_from(&many));
+    assert!(many.keys_disjoint_from(&far));
+}
+
+#[test]
+fn from_iter_duplicates() {
+    let map = [(2, 1), (1, 1), (2, 2), (1, 2), (2, 3)]
+        .into_iter()
+        .collect::<Map<_, _>>();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(&1), Some(&2));
+    assert_eq!(map.get(&2), Some(&3));
+}
+
+#[test]
+fn range() {
+    let map = (0..10).map(|i| (i * 2, i)).collect::<Map<_, _>>();
+    assert!(map.range(&4..&10).map(|field| field.value).eq([2, 3, 4]));
+    assert!(map.range(&5..=&10).map(|field| field.value).eq([3, 4, 5]));
+    assert!(map.range(..&3).rev().map(|field| *field.key()).eq([2, 0]));
+    assert_eq!(map.range(&19..).count(), 0);
+    assert_eq!(map.range::<i32, _>(..).len(), 10);
+    assert!(map.into_keys().rev().take(2).eq([18, 16]));
+}
+
+#[test]
+fn btree_interop() {
+    let btree = [("b", 2), ("a", 1), ("c", 3)]
+        .into_iter()
+        .collect::<BTreeMap<_, _>>();
+    let map = Map::from(btree.clone());
+    assert_eq!(map, btree);
+    assert_eq!(btree, map);
+    assert_eq!(BTreeMap::from(map.clone()), btree);
+    assert_eq!(map, Map::from([("c", 3), ("a", 1), ("b", 2)]));
+    assert_eq!(map, Map::from(vec![("a", 1), ("b", 2), ("c", 3)]));
+
+    let mut modified = map.clone();
+    modified.insert("b", 4);
+    assert_ne!(modified, btree);
+    modified.remove(&"b");
+    assert_ne!(modified, btree);
+}
+
+#[test]
+fn diff_and_patch() {
+    let old = Map::from([("a", 1), ("b", 2), ("c", 3), ("e", 5)]);
+    let new = Map::from([("b", 2), ("c", 4), ("d", 4), ("e", 5)]);
+    let changes = old.diff(&new).collect::<Vec<_>>();
+    assert_eq!(
+        changes,
+        [
+            Change::Removed {
+                key: &"a",
+                value: &1
+            },
+            Change::Modified {
+                key: &"c",
+                old: &3,
+                new: &4
+            },
+            Change::Added {
+                key: &"d",
+                value: &4
+            },
+        ]
+    );
+    assert_eq!(
+        old.diff(&new)
+            .rev()
+            .map(|change| *change.key())
+            .collect::<Vec<_>>(),
+        ["d", "c", "a"]
+    );
+    assert_eq!(old.diff(&old).next(), None);
+
+    let patch = old.diff(&new).collect::<MapPatch<_, _>>();
+    assert_eq!(patch.len(), 3);
+    assert_eq!(patch.get(&"a"), Some(&Edit::Remove(1)));
+    assert_eq!(patch.get(&"c"), Some(&Edit::Replace { old: 3, new: 4 }));
+    assert_eq!(patch.get(&"d"), Some(&Edit::Insert(4)));
+    assert_eq!(patch.get(&"b"), None);
+
+    let mut patched = old.clone();
+    patch.apply(&mut patched);
+    assert_eq!(patched, new);
+
+    let inverse = patch.clone().inverse();
+    assert_eq!(inverse.get(&"a"), Some(&Edit::Insert(1)));
+    inverse.apply(&mut patched);
+    assert_eq!(patched, old);
+
+    // Edits are applied regardless of the map's current contents.
+    let mut patched = Map::from([("c", 0), ("z", 26)]);
+    patch.apply(&mut patched);
+    assert_eq!(patched, Map::from([("c", 4), ("d", 4), ("z", 26)]));
+
+    let mut manual = MapPatch::new();
+    assert!(manual.is_empty());
+    assert_eq!(manual.insert("a", Edit::Insert(1)), None);
+    assert_eq!(manual.iter().count(), 1);
+    let mut empty = Map::new();
+    manual.apply(&mut empty);
+    assert_eq!(empty, Map::from([("a", 1)]));
+}
+
+#[test]
+fn merge3() {
+    let base = Map::from([("a", 1), ("b", 2), ("c", 3), ("d", 4), ("e", 5), ("f", 6)]);
+    // a: unchanged, b: edited by ours, c: edited by theirs, d: same edit on
+    // both, e: removed by theirs, f: removed by both, g: added by ours, h: same
+    // add on both.
+    let ours = Map::from([
+        ("a", 1),
+        ("b", 20),
+        ("c", 3),
+        ("d", 40),
+        ("e", 5),
+        ("g", 7),
+        ("h", 8),
+    ]);
+    let theirs = Map::from([("a", 1), ("b", 2), ("c", 30), ("d", 40), ("h", 8)]);
+    let merged = Map::merge3(&base, &ours, &theirs, |_, _| unreachable!("no conflicts")).unwrap();
+    assert_eq!(
+        merged,
+        Map::from([
+            ("a", 1),
+            ("b", 20),
+            ("c", 30),
+            ("d", 40),
+            ("g", 7),
+            ("h", 8)
+        ])
+    );
+
+    // Each kind of conflict.
+    let base = Map::from([("edit-edit", 1), ("edit-delete", 2), ("delete-edit", 3)]);
+    let ours = Map::from([("edit-edit", 10), ("edit-delete", 20), ("add-add", 40)]);
+    let theirs = Map::from([("edit-edit", 100), ("delete-edit", 300), ("add-add", 400)]);
+    let mut seen = Vec::new();
+    let merged = Map::merge3(&base, &ours, &theirs, |key, conflict| {
+        seen.push((*key, conflict));
+        match conflict {
+            Conflict::EditEdit { theirs, .. } => Resolution::Keep(*theirs),
+            Conflict::AddAdd { ours, theirs } => Resolution::Keep(ours + theirs),
+            Conflict::EditDelete { .. } | Conflict::DeleteEdit { .. } => Resolution::Remove,
+        }
+    })
+    .unwrap();
+    assert_eq!(merged, Map::from([("add-add", 440), ("edit-edit", 100)]));
+    assert_eq!(
+        seen,
+        [
+            (
+                "add-add",
+                Conflict::AddAdd {
+                    ours: &40,
+                    theirs: &400
+                }
+            ),
+            (
+                "delete-edit",
+                Conflict::DeleteEdit {
+                    base: &3,
+                    theirs: &300
+                }
+            ),
+            (
+                "edit-delete",
+                Conflict::EditDelete {
+                    base: &2,
+                    ours: &20
+                }
+            ),
+            (
+                "edit-edit",
+                Conflict::EditEdit {
+                    base: &1,
+                    ours: &10,
+                    theirs: &100
+                }
+            ),
+        ]
+    );
+
+    let unresolved = Map::merge3(&base, &ours, &theirs, |key, _| {
+        if *key == "edit-edit" {
+            Resolution::Keep(0)
+        } else {
+            Resolution::Unresolved
+        }
+    })
+    .unwrap_err();
+    assert_eq!(
+        unresolved.iter().map(|(key, _)| **key).collect::<Vec<_>>(),
+        ["add-add", "delete-edit", "edit-delete"]
+    );
+}
+
+#[test]
+fn merge_from() {
+    #[derive(Ord, PartialOrd, Eq, PartialEq, Debug)]
+    struct NotCloneable(usize);
+
+    let mut evens = (0..20)
+        .step_by(2)
+        .map(|i| (NotCloneable(i), vec![i]))
+        .collect::<Map<_, _>>();
+    let threes = (0..20)
+        .step_by(3)
+        .map(|i| (NotCloneable(i), vec![i * 10]))
+        .collect::<Map<_, _>>();
+    evens.merge_from(threes, |key, existing, mut incoming| {
+        assert_eq!(key.0 % 6, 0);
+        existing.append(&mut incoming);
+    });
+    assert_eq!(evens.len(), 13);
+    assert_eq!(evens.get(&NotCloneable(0)), Some(&vec![0, 0]));
+    assert_eq!(evens.get(&NotCloneable(2)), Some(&vec![2]));
+    assert_eq!(evens.get(&NotCloneable(3)), Some(&vec![30]));
+    assert_eq!(evens.get(&NotCloneable(18)), Some(&vec![18, 180]));
+    assert!(evens
+        .keys()
+        .map(|key| key.0)
+        .eq([0, 2, 3, 4, 6, 8, 9, 10, 12, 14, 15, 16, 18]));
+
+    let mut empty = Map::new();
+    empty.merge_from(Map::from([(1, 1)]), |_, _, _| unreachable!());
+    assert_eq!(empty, Map::from([(1, 1)]));
+    empty.merge_from(Map::new(), |_, _, _| unreachable!());
+    assert_eq!(empty.len(), 1);
+}
+
+#[test]
+fn merge_from_panic() {
+    let mut map = Map::from([(1, 1), (2, 2), (3, 3), (4, 4)]);
+    let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+        map.merge_from(Map::from([(0, 0), (1, 10), (3, 30)]), |key, _, _| {
+            assert_ne!(*key, 3);
+        });
+    }));
+    assert!(result.is_err());
+    assert_eq!(map, Map::from([(0, 0), (1, 1), (2, 2), (3, 3), (4, 4)]));
+}
+
+#[test]
+fn merge_many() {
+    let maps = (1..=4)
+        .map(|step| {
+            (0..50)
+                .step_by(step)
+                .map(|i| (i, i * step))
+                .collect::<Map<_, _>>()
+        })
+        .collect::<Vec<_>>();
+    let merged = map::merge_many(&maps).collect::<Vec<_>>();
+    assert_eq!(merged.len(), 50);
+    for (key, values) in merged {
+        let expected = (1..=4)
+            .filter(|step| key % step == 0)
+            .map(|step| (step - 1, key * step))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values
+                .into_iter()
+                .map(|(source, value)| (source, *value))
+                .collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    let mut iter = map::merge_many([&maps[1], &maps[3]]);
+    assert_eq!(iter.size_hint(), (25, Some(38)));
+    assert_eq!(iter.next(), Some((&0, vec![(0, &0), (1, &0)])));
+    assert_eq!(iter.next(), Some((&2, vec![(0, &4)])));
+    assert_eq!(iter.nth(22), Some((&48, vec![(0, &96), (1, &192)])));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next(), None);
+
+    assert_eq!(map::merge_many::<usize, usize>([]).next(), None);
+    let empty = Map::new();
+    assert!(map::merge_many([&empty, &maps[3], &empty])
+        .map(|(key, _)| *key)
+        .eq((0..50).step_by(4)));
+}
+
+#[test]
+fn intersect_many() {
+    let maps = (2..=4)
+        .map(|step| {
+            (0..50)
+                .step_by(step)
+                .map(|i| (i, i * step))
+                .collect::<Map<_, _>>()
+        })
+        .collect::<Vec<_>>();
+    let shared = map::intersect_many(&maps).collect::<Vec<_>>();
+    assert_eq!(
+        shared,
+        [
+            (&0, vec![&0, &0, &0]),
+            (&12, vec![&24, &36, &48]),
+            (&24, vec![&48, &72, &96]),
+            (&36, vec![&72, &108, &144]),
+            (&48, vec![&96, &144, &192]),
+        ]
+    );
+    assert_eq!(map::intersect_many(&maps).size_hint(), (0, Some(13)));
+
+    // A single map is returned in its entirety.
+    assert_eq!(map::intersect_many(&maps[..1]).count(), 25);
+    assert_eq!(map::intersect_many::<usize, usize>([]).next(), None);
+    assert_eq!(map::intersect_many([&maps[0], &Map::new()]).next(), None);
+}
+
+#[test]
+fn set_operations_reversed() {
+    let a = Map::from([(1, 'a'), (3, 'c'), (5, 'e'), (7, 'g')]);
+    let b = Map::from([(3, 'C'), (4, 'D'), (7, 'G')]);
+
+    let union = a.union(&b);
+    assert_eq!(union.size_hint(), (4, Some(7)));
+    assert!(union
+        .rev()
+        .map(|unioned| unioned.map_both(|_, _, right| right).value.into_owned())
+        .eq(['G', 'e', 'D', 'C', 'a'].into_iter()));
+
+    let mut intersection = a.intersection(&b);
+    assert_eq!(intersection.size_hint(), (0, Some(3)));
+    assert_eq!(intersection.next_back(), Some((&7, &'g', &'G')));
+    assert_eq!(intersection.next(), Some((&3, &'c', &'C')));
+    assert_eq!(intersection.next_back(), None);
+    assert_eq!(intersection.next(), None);
+
+    let difference = a.difference(&b);
+    assert_eq!(difference.size_hint(), (1, Some(4)));
+    assert!(difference.rev().eq([(&5, &'e'), (&1, &'a')]));
+    assert_eq!(b.difference(&a).size_hint(), (0, Some(3)));
+}
+
+#[test]
+fn galloping_set_operations() {
+    let large = (0..100_000_u32).map(|i| (i * 2, i)).collect::<Map<_, _>>();
+    let small = [0, 3, 1_000, 1_001, 77_776, 199_998, 250_000]
+        .into_iter()
+        .map(|key| (key, key))
+        .collect::<Map<_, _>>();
+    let shared = [0, 1_000, 77_776, 199_998];
+    let small_only = [3, 1_001, 250_000];
+
+    for (a, b) in [(&small, &large), (&large, &small)] {
+        assert!(a
+            .intersection(b)
+            .map(|(key, _, _)| *key)
+            .eq(shared.into_iter()));
+        assert!(a
+            .intersection(b)
+            .rev()
+            .map(|(key, _, _)| *key)
+            .eq(shared.into_iter().rev()));
+    }
+
+    assert!(small
+        .difference(&large)
+        .map(|(key, _)| *key)
+        .eq(small_only.into_iter()));
+    assert!(small
+        .difference(&large)
+        .rev()
+        .map(|(key, _)| *key)
+        .eq(small_only.into_iter().rev()));
+    assert_eq!(large.difference(&small).count(), 100_000 - shared.len());
+    assert_eq!(
+        large.difference(&small).next_back(),
+        Some((&199_996, &99_998))
+    );
+
+    let subset = shared
+        .into_iter()
+        .map(|key| (key, ()))
+        .collect::<Map<_, _>>();
+    assert!(subset.keys_subset_of(&large));
+    assert!(large.keys_superset_of(&subset));
+    assert!(!small.keys_subset_of(&large));
+    let disjoint = small_only
+        .into_iter()
+        .map(|key| (key, ()))
+        .collect::<Map<_, _>>();
+    assert!(disjoint.keys_disjoint_from(&large));
+    assert!(large.keys_disjoint_from(&disjoint));
+    assert!(!subset.keys_disjoint_from(&large));
+
+    let mut merged = large.clone();
+    merged.merge_with(
+        &small,
+        |_, value| Some(*value),
+        |_, existing, incoming| *existing += incoming,
+    );
+    assert_eq!(merged.len(), 100_000 + small_only.len());
+    assert_eq!(merged.get(&77_776), Some(&(38_888 + 77_776)));
+    assert_eq!(merged.get(&1_001), Some(&1_001));
+    assert_eq!(merged.get(&199_996), Some(&99_998));
+}
+
+#[test]
+fn intersection_mut() {
+    let mut balances = Map::from([(1, 10_i64), (2, 20), (3, 30), (5, 50)]);
+    let deltas = Map::from([(0, "zero"), (2, "two"), (3, "three"), (4, "four")]);
+    for (_, balance, delta) in balances.intersection_mut(&deltas) {
+        *balance += i64::try_from(delta.len()).unwrap();
+    }
+    assert_eq!(balances, Map::from([(1, 10), (2, 23), (3, 35), (5, 50)]));
+
+    let mut iter = balances.intersection_mut(&deltas);
+    assert_eq!(iter.size_hint(), (0, Some(4)));
+    let (key, value, delta) = iter.next_back().unwrap();
+    assert_eq!((key, *value, delta), (&3, 35, &"three"));
+    *value = 0;
+    assert_eq!(iter.next().map(|(key, _, _)| *key), Some(2));
+    assert!(iter.next().is_none());
+    assert!(iter.next_back().is_none());
+    assert_eq!(balances.get(&3), Some(&0));
+
+    // Unbalanced maps gallop through the larger side.
+    let mut large = (0..10_000_u32).map(|i| (i, i)).collect::<Map<_, _>>();
+    let small = Map::from([(5, ()), (9_999, ()), (20_000, ())]);
+    for (_, value, ()) in large.intersection_mut(&small) {
+        *value = 0;
+    }
+    assert_eq!(large.get(&5), Some(&0));
+    assert_eq!(large.get(&9_999), Some(&0));
+    assert_eq!(large.get(&6), Some(&6));
+    let mut small = small;
+    assert!(small
+        .intersection_mut(&large)
+        .rev()
+        .map(|(key, (), value)| (*key, *value))
+        .eq([(9_999, 0), (5, 0)]));
+}
+
+#[test]
+fn joins() {
+    struct Unique(u8);
+
+    let names = Map::from([(1, "one"), (2, "two"), (4, "four")]);
+    let scores = Map::from([(0, 0_u8), (2, 20), (3, 30), (4, 40)]);
+
+    assert_eq!(
+        names.inner_join(&scores),
+        Map::from([(2, ("two", 20)), (4, ("four", 40))])
+    );
+    assert_eq!(
+        names.left_join(&scores),
+        Map::from([
+            (1, ("one", None)),
+            (2, ("two", Some(20))),
+            (4, ("four", Some(40)))
+        ])
+    );
+    let outer = names.outer_join(&scores);
+    assert_eq!(
+        outer,
+        Map::from([
+            (0, EitherOrBoth::Right(0)),
+            (1, EitherOrBoth::Left("one")),
+            (2, EitherOrBoth::Both("two", 20)),
+            (3, EitherOrBoth::Right(30)),
+            (4, EitherOrBoth::Both("four", 40)),
+        ])
+    );
+    assert_eq!(outer[&2].left(), Some(&"two"));
+    assert_eq!(outer[&3].left(), None);
+    assert_eq!(outer[&1].right(), None);
+    assert!(outer[&4].is_both());
+    assert_eq!(outer[&0].into_parts(), (None, Some(0)));
+
+    // The consuming forms don't require values to implement Clone.
+    let uniques = || Map::from([(2, Unique(2)), (3, Unique(3))]);
+    let inner = names.clone().into_inner_join(uniques());
+    assert!(inner
+        .iter()
+        .map(|field| (*field.key(), field.value.0, field.value.1 .0))
+        .eq([(2, "two", 2)]));
+    let left = uniques().into_left_join(names.clone());
+    assert!(left
+        .iter()
+        .map(|field| (*field.key(), field.value.0 .0, field.value.1))
+        .eq([(2, 2, Some("two")), (3, 3, None)]));
+    let outer = names.clone().into_outer_join(uniques());
+    assert!(outer
+        .iter()
+        .map(|field| (
+            *field.key(),
+            field.value.left().copied(),
+            field.value.right().map(|unique| unique.0)
+        ))
+        .eq([
+            (1, Some("one"), None),
+            (2, Some("two"), Some(2)),
+            (3, None, Some(3)),
+            (4, Some("four"), None)
+        ]));
+
+    assert!(names.inner_join(&Map::<i32, ()>::new()).is_empty());
+    assert_eq!(Map::<i32, ()>::new().outer_join(&names).len(), 3);
+
+    // Inner joins of unbalanced maps gallop through the larger map.
+    let large = (0..1_000).map(|key| (key, key * 10)).collect::<Map<_, _>>();
+    let small = Map::from([(-1, 'a'), (10, 'b'), (500, 'c'), (999, 'd')]);
+    let expected = Map::from([(10, ('b', 100)), (500, ('c', 5_000)), (999, ('d', 9_990))]);
+    assert_eq!(small.inner_join(&large), expected);
+    assert_eq!(small.clone().into_inner_join(large.clone()), expected);
+    assert_eq!(
+        large
+            .into_inner_join(small)
+            .into_iter()
+            .map(Field::into_parts)
+            .collect::<Vec<_>>(),
+        [(10, (100, 'b')), (500, (5_000, 'c')), (999, (9_990, 'd'))]
+    );
+}
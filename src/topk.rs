@@ -0,0 +1,420 @@
+use core::cmp::Ordering;
+use core::fmt::{self, Debug};
+use core::iter;
+
+use crate::map::{self, Field};
+use crate::{Map, Sort};
+
+/// An ordered map that keeps at most `capacity` fields, retaining only the
+/// fields with the largest or smallest keys.
+///
+/// When inserting a field causes this collection to exceed its capacity, the
+/// field with the worst key is evicted and returned.
+///
+/// ```rust
+/// use kempt::TopK;
+///
+/// let mut high_scores = TopK::largest(2);
+/// assert_eq!(high_scores.insert(10, "a"), None);
+/// assert_eq!(high_scores.insert(30, "b"), None);
+/// let evicted = high_scores.insert(20, "c").expect("full");
+/// assert_eq!(evicted.into_parts(), (10, "a"));
+/// assert!(!high_scores.would_accept(&5));
+/// assert!(high_scores.iter().map(|field| field.key()).eq(&[20, 30]));
+/// ```
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct TopK<K, V>
+where
+    K: Sort<K>,
+{
+    fields: Map<K, V>,
+    capacity: usize,
+    keep: Ordering,
+}
+
+impl<K, V> TopK<K, V>
+where
+    K: Sort<K>,
+{
+    /// Returns an empty collection that holds at most `capacity` fields,
+    /// keeping the fields whose keys are furthest in the direction of `keep`.
+    ///
+    /// [`Ordering::Greater`] keeps the largest keys, and [`Ordering::Less`]
+    /// keeps the smallest keys.
+    ///
+    /// # Panics
+    ///
+    /// A panic will occur if `keep` is [`Ordering::Equal`].
+    #[must_use]
+    #[inline]
+    pub const fn new(capacity: usize, keep: Ordering) -> Self {
+        assert!(
+            !matches!(keep, Ordering::Equal),
+            "keep must be Ordering::Greater or Ordering::Less"
+        );
+        Self {
+            fields: Map::new(),
+            capacity,
+            keep,
+        }
+    }
+
+    /// Returns an empty collection that keeps the `capacity` fields with the
+    /// largest keys.
+    #[must_use]
+    #[inline]
+    pub const fn largest(capacity: usize) -> Self {
+        Self::new(capacity, Ordering::Greater)
+    }
+
+    /// Returns an empty collection that keeps the `capacity` fields with the
+    /// smallest keys.
+    #[must_use]
+    #[inline]
+    pub const fn smallest(capacity: usize) -> Self {
+        Self::new(capacity, Ordering::Less)
+    }
+
+    /// Returns the maximum number of fields this collection can contain.
+    #[must_use]
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the direction of keys this collection keeps.
+    /// [`Ordering::Greater`] indicates the largest keys are kept.
+    #[must_use]
+    #[inline]
+    pub const fn keep(&self) -> Ordering {
+        self.keep
+    }
+
+    /// Returns true if inserting `key` would add it to this collection.
+    ///
+    /// This is true if the collection is not full, if `key` is already
+    /// contained in the collection, or if `key` is better than the current
+    /// worst key. This function can be used to avoid building a value that
+    /// would be rejected.
+    #[inline]
+    pub fn would_accept<SearchFor>(&self, key: &SearchFor) -> bool
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        if self.fields.len() < self.capacity {
+            return true;
+        }
+        self.worst()
+            .map_or(false, |worst| worst.key().compare(key) != self.keep)
+    }
+
+    /// Inserts `key` and `value`, returning the field that no longer fits in
+    /// this collection.
+    ///
+    /// - If `key` is already contained, its value is replaced and the previous
+    ///   field is returned.
+    /// - If this collection is full and `key` is better than the worst key,
+    ///   the worst field is evicted and returned.
+    /// - If this collection is full and `key` is not better than the worst
+    ///   key, the new field is returned without modifying this collection.
+    #[inline]
+    pub fn insert(&mut self, key: K, value: V) -> Option<Field<K, V>> {
+        if !self.would_accept(&key) {
+            return Some(Field::new(key, value));
+        }
+        let replaced = self.fields.insert(key, value);
+        if replaced.is_some() {
+            return replaced;
+        }
+        if self.fields.len() > self.capacity {
+            self.remove_worst()
+        } else {
+            None
+        }
+    }
+
+    /// Merges the fields from `other` into this collection, keeping only the
+    /// best `capacity` fields. If both collections contain the same key, the
+    /// value from `other` is kept.
+    ///
+    /// Both collections are merged in a single pass.
+    ///
+    /// ```rust
+    /// use kempt::TopK;
+    ///
+    /// let mut a = TopK::smallest(3);
+    /// a.extend([(1, 'a'), (4, 'a'), (6, 'a')]);
+    /// let mut b = TopK::smallest(3);
+    /// b.extend([(2, 'b'), (4, 'b'), (5, 'b')]);
+    /// a.merge(b);
+    /// assert!(a
+    ///     .iter()
+    ///     .map(|field| (*field.key(), field.value))
+    ///     .eq([(1, 'a'), (2, 'b'), (4, 'b')]));
+    /// ```
+    pub fn merge(&mut self, other: Self) {
+        self.fields
+            .merge_from(other.fields, |_, existing, incoming| *existing = incoming);
+        let excess = self.fields.len().saturating_sub(self.capacity);
+        if excess > 0 {
+            let worst = match self.keep {
+                Ordering::Greater => 0..excess,
+                _ => self.capacity..self.fields.len(),
+            };
+            self.fields.splice_fields(worst, iter::empty());
+        }
+    }
+
+    /// Returns the value associated with `key`, if found.
+    #[inline]
+    pub fn get<SearchFor>(&self, key: &SearchFor) -> Option<&V>
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.fields.get(key)
+    }
+
+    /// Returns a mutable reference to the value associated with `key`, if
+    /// found.
+    #[inline]
+    pub fn get_mut<SearchFor>(&mut self, key: &SearchFor) -> Option<&mut V>
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.fields.get_mut(key)
+    }
+
+    /// Returns true if this collection contains `key`.
+    #[inline]
+    pub fn contains<SearchFor>(&self, key: &SearchFor) -> bool
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.fields.contains(key)
+    }
+
+    /// Removes the field associated with `key`, returning it if found.
+    #[inline]
+    pub fn remove<SearchFor>(&mut self, key: &SearchFor) -> Option<Field<K, V>>
+    where
+        K: Sort<SearchFor>,
+        SearchFor: ?Sized,
+    {
+        self.fields.remove(key)
+    }
+
+    /// Returns the field with the best key in this collection.
+    #[must_use]
+    #[inline]
+    pub fn best(&self) -> Option<&Field<K, V>> {
+        self.fields.field(self.index_of(self.keep)?)
+    }
+
+    /// Returns the field with the worst key in this collection. This is the
+    /// field that will be evicted next.
+    #[must_use]
+    #[inline]
+    pub fn worst(&self) -> Option<&Field<K, V>> {
+        self.fields.field(self.index_of(self.keep.reverse())?)
+    }
+
+    /// Removes and returns the field with the worst key in this collection.
+    #[inline]
+    pub fn remove_worst(&mut self) -> Option<Field<K, V>> {
+        let index = self.index_of(self.keep.reverse())?;
+        Some(self.fields.remove_by_index(index))
+    }
+
+    fn index_of(&self, end: Ordering) -> Option<usize> {
+        match end {
+            Ordering::Greater => self.fields.len().checked_sub(1),
+            _ => (!self.fields.is_empty()).then_some(0),
+        }
+    }
+
+    /// Returns the number of fields in this collection.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Returns true if this collection contains no fields.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Returns true if this collection contains `capacity` fields.
+    #[must_use]
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.fields.len() >= self.capacity
+    }
+
+    /// Returns an iterator over the fields in this collection, in the sort
+    /// order of the `K` type.
+    #[must_use]
+    #[inline]
+    pub fn iter(&self) -> map::Iter<'_, K, V> {
+        self.into_iter()
+    }
+
+    /// Returns the fields in this collection as a [`Map`].
+    #[must_use]
+    #[inline]
+    pub fn as_map(&self) -> &Map<K, V> {
+        &self.fields
+    }
+
+    /// Returns the fields in this collection as a [`Map`].
+    #[must_use]
+    #[inline]
+    pub fn into_map(self) -> Map<K, V> {
+        self.fields
+    }
+
+    /// Removes all fields from this collection.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.fields.clear();
+    }
+}
+
+impl<K, V> Debug for TopK<K, V>
+where
+    K: Sort<K> + Debug,
+    V: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_map();
+        for field in self {
+            s.entry(field.key(), &field.value);
+        }
+        s.finish()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a TopK<K, V>
+where
+    K: Sort<K>,
+{
+    type IntoIter = map::Iter<'a, K, V>;
+    type Item = &'a Field<K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.fields.iter()
+    }
+}
+
+impl<K, V> IntoIterator for TopK<K, V>
+where
+    K: Sort<K>,
+{
+    type IntoIter = map::IntoIter<K, V>;
+    type Item = Field<K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.fields.into_iter()
+    }
+}
+
+impl<K, V> Extend<(K, V)> for TopK<K, V>
+where
+    K: Sort<K>,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+#[test]
+fn largest() {
+    let mut top = TopK::largest(3);
+    assert!(top.is_empty());
+    assert_eq!(top.insert(5, 'a'), None);
+    assert_eq!(top.insert(1, 'b'), None);
+    assert_eq!(top.insert(3, 'c'), None);
+    assert!(top.is_full());
+    assert_eq!(top.best().map(Field::key), Some(&5));
+    assert_eq!(top.worst().map(Field::key), Some(&1));
+
+    // Replacing an existing key returns the previous field.
+    assert_eq!(top.insert(3, 'd').map(Field::into_parts), Some((3, 'c')));
+    // Better keys evict the worst field.
+    assert_eq!(top.insert(4, 'e').map(Field::into_parts), Some((1, 'b')));
+    // Worse keys are returned without modifying the collection.
+    assert!(!top.would_accept(&2));
+    assert_eq!(top.insert(2, 'f').map(Field::into_parts), Some((2, 'f')));
+    assert!(top.would_accept(&3));
+    assert!(top
+        .iter()
+        .map(|field| (*field.key(), field.value))
+        .eq([(3, 'd'), (4, 'e'), (5, 'a')]));
+
+    assert_eq!(top.remove_worst().map(Field::into_parts), Some((3, 'd')));
+    assert!(top.would_accept(&0));
+    assert_eq!(top.remove(&5).map(Field::into_parts), Some((5, 'a')));
+    assert_eq!(top.get(&4), Some(&'e'));
+    assert!(top.contains(&4));
+    assert_eq!(top.len(), 1);
+    top.clear();
+    assert_eq!(top.remove_worst(), None);
+    assert_eq!(top.capacity(), 3);
+}
+
+#[test]
+fn smallest() {
+    let mut bottom = TopK::smallest(2);
+    assert_eq!(bottom.keep(), Ordering::Less);
+    bottom.extend([(5, ()), (1, ()), (3, ())]);
+    assert!(bottom.into_map().into_keys().eq([1, 3]));
+
+    let mut bottom = TopK::smallest(2);
+    bottom.extend([(5, ()), (1, ())]);
+    assert!(!bottom.would_accept(&6));
+    assert!(bottom.would_accept(&5));
+    assert!(bottom.would_accept(&4));
+    assert_eq!(bottom.insert(4, ()).map(Field::into_key), Some(5));
+    assert_eq!(bottom.best().map(Field::key), Some(&1));
+    assert_eq!(bottom.worst().map(Field::key), Some(&4));
+
+    let mut empty = TopK::smallest(0);
+    assert!(!empty.would_accept(&0));
+    assert_eq!(empty.insert(0, ()).map(Field::into_key), Some(0));
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn merge() {
+    let mut a = TopK::largest(3);
+    a.extend([(1, 'a'), (4, 'a'), (6, 'a')]);
+    let mut b = TopK::largest(3);
+    b.extend([(2, 'b'), (4, 'b'), (5, 'b')]);
+    a.merge(b.clone());
+    assert!(a
+        .iter()
+        .map(|field| (*field.key(), field.value))
+        .eq([(4, 'b'), (5, 'b'), (6, 'a')]));
+
+    let mut empty = TopK::largest(2);
+    empty.merge(b);
+    assert!(empty.into_iter().map(Field::into_key).eq([4, 5]));
+}
+
+#[test]
+#[should_panic = "keep must be Ordering::Greater or Ordering::Less"]
+fn equal_keep() {
+    let _ = TopK::<u8, ()>::new(1, Ordering::Equal);
+}